@@ -0,0 +1,73 @@
+use crate::ConfgrError;
+use std::fs;
+use std::path::Path;
+
+/// Key used by configuration files to include other files, e.g. `include = ["common.toml"]`, if
+/// the struct sets the `includes` attribute.
+pub const INCLUDE_KEY: &str = "include";
+
+/// Expands the `include` key of a configuration file and of every file it includes.
+///
+/// Included paths are resolved relative to the including file. The returned files are in ascending
+/// order of precedence, so each file comes after the files it includes.
+///
+/// # Errors
+///
+/// Returns [`ConfgrError::IncludeCycle`] if a file includes itself, directly or indirectly, and
+/// [`ConfgrError::MissingInclude`] if an included file does not exist.
+pub fn resolve_includes(path: &str) -> Result<Vec<String>, ConfgrError> {
+    let mut files = Vec::new();
+    resolve(path, &mut Vec::new(), &mut files)?;
+    Ok(files)
+}
+
+fn resolve(
+    path: &str,
+    chain: &mut Vec<String>,
+    files: &mut Vec<String>,
+) -> Result<(), ConfgrError> {
+    let identity = canonical(path);
+
+    if chain.iter().any(|included| canonical(included) == identity) {
+        chain.push(path.to_string());
+        return Err(ConfgrError::IncludeCycle(chain.join(" -> ")));
+    }
+
+    let config = config::Config::builder()
+        .add_source(config::File::with_name(path))
+        .build()?;
+
+    let includes = match config.get::<Vec<String>>(INCLUDE_KEY) {
+        Ok(includes) => includes,
+        Err(config::ConfigError::NotFound(_)) => Vec::new(),
+        Err(_) => vec![config.get::<String>(INCLUDE_KEY)?],
+    };
+
+    chain.push(path.to_string());
+
+    for include in includes {
+        let included = Path::new(path)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&include)
+            .to_string_lossy()
+            .into_owned();
+
+        if !Path::new(&included).exists() {
+            return Err(ConfgrError::MissingInclude(included, chain.join(" -> ")));
+        }
+
+        resolve(&included, chain, files)?;
+    }
+
+    chain.pop();
+    files.push(path.to_string());
+
+    Ok(())
+}
+
+fn canonical(path: &str) -> String {
+    fs::canonicalize(path)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
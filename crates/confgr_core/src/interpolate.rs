@@ -0,0 +1,171 @@
+use crate::ConfgrError;
+use config::{Value, ValueKind};
+
+/// Resolves `${...}` expressions in every string of a configuration value tree.
+///
+/// - `${section.key}`, any name containing a `.`, is replaced with another value of the same tree.
+/// - `${NAME}` is replaced with the top level key `NAME` of the same tree if it is set, and with the
+///   environment variable `NAME` otherwise.
+/// - `${NAME:-fallback}` uses `fallback` when the variable or key is not defined.
+/// - `$${` is an escaped, literal `${`.
+///
+/// # Errors
+///
+/// Returns [`ConfgrError::Interpolation`] for undefined variables, unterminated expressions and
+/// reference cycles between keys.
+pub fn interpolate(value: Value) -> Result<Value, ConfgrError> {
+    interpolate_with(value, |name| std::env::var(name).ok())
+}
+
+/// Identical to [`interpolate()`], but resolves environment variables through `lookup` instead of
+/// reading the process environment.
+///
+/// # Errors
+///
+/// Returns [`ConfgrError::Interpolation`] for undefined variables, unterminated expressions and
+/// reference cycles between keys.
+pub fn interpolate_with<F>(value: Value, lookup: F) -> Result<Value, ConfgrError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let root = value.clone();
+    resolve_value(value, &root, &lookup, &mut Vec::new(), "")
+}
+
+fn resolve_value(
+    mut value: Value,
+    root: &Value,
+    env: &dyn Fn(&str) -> Option<String>,
+    stack: &mut Vec<String>,
+    key: &str,
+) -> Result<Value, ConfgrError> {
+    value.kind = match value.kind {
+        ValueKind::String(string) => {
+            stack.push(key.to_string());
+            let resolved = resolve_str(&string, root, env, stack, key)?;
+            stack.pop();
+            ValueKind::String(resolved)
+        }
+        ValueKind::Table(table) => ValueKind::Table(
+            table
+                .into_iter()
+                .map(|(name, value)| {
+                    let path = if key.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{}.{}", key, name)
+                    };
+                    Ok((name, resolve_value(value, root, env, stack, &path)?))
+                })
+                .collect::<Result<_, ConfgrError>>()?,
+        ),
+        ValueKind::Array(array) => ValueKind::Array(
+            array
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    resolve_value(value, root, env, stack, &format!("{}[{}]", key, index))
+                })
+                .collect::<Result<_, ConfgrError>>()?,
+        ),
+        kind => kind,
+    };
+
+    Ok(value)
+}
+
+fn resolve_str(
+    string: &str,
+    root: &Value,
+    env: &dyn Fn(&str) -> Option<String>,
+    stack: &mut Vec<String>,
+    key: &str,
+) -> Result<String, ConfgrError> {
+    let mut resolved = String::new();
+    let mut rest = string;
+
+    while let Some(start) = rest.find('$') {
+        resolved.push_str(&rest[..start]);
+        let expression = &rest[start..];
+
+        if let Some(escaped) = expression.strip_prefix("$${") {
+            resolved.push_str("${");
+            rest = escaped;
+            continue;
+        }
+
+        let Some(body) = expression.strip_prefix("${") else {
+            resolved.push('$');
+            rest = &expression[1..];
+            continue;
+        };
+
+        let end = body.find('}').ok_or_else(|| {
+            ConfgrError::Interpolation(format!("Unterminated '${{' in '{}'", key))
+        })?;
+
+        let (name, fallback) = match body[..end].split_once(":-") {
+            Some((name, fallback)) => (name, Some(fallback)),
+            None => (&body[..end], None),
+        };
+
+        let value = match lookup_key(name, root, env, stack)? {
+            Some(value) => Some(value),
+            None if name.contains('.') => None,
+            None => env(name),
+        };
+
+        match value.or_else(|| fallback.map(str::to_string)) {
+            Some(value) => resolved.push_str(&value),
+            None => {
+                return Err(ConfgrError::Interpolation(format!(
+                    "Undefined variable '{}' in '{}'",
+                    name, key
+                )))
+            }
+        }
+
+        rest = &body[end + 1..];
+    }
+
+    resolved.push_str(rest);
+
+    Ok(resolved)
+}
+
+fn lookup_key(
+    path: &str,
+    root: &Value,
+    env: &dyn Fn(&str) -> Option<String>,
+    stack: &mut Vec<String>,
+) -> Result<Option<String>, ConfgrError> {
+    if stack.iter().any(|key| key == path) {
+        stack.push(path.to_string());
+        return Err(ConfgrError::Interpolation(format!(
+            "Reference cycle: {}",
+            stack.join(" -> ")
+        )));
+    }
+
+    let mut current = root;
+
+    for segment in path.split('.') {
+        match &current.kind {
+            ValueKind::Table(table) => match table.get(segment) {
+                Some(value) => current = value,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        }
+    }
+
+    let Ok(raw) = current.clone().into_string() else {
+        return Ok(None);
+    };
+
+    stack.push(path.to_string());
+    let resolved = resolve_str(&raw, root, env, stack, path)?;
+    stack.pop();
+
+    Ok(Some(resolved))
+}
@@ -0,0 +1,314 @@
+use crate::{warn, ConfgrError, Source, Warning};
+use config::{Value, ValueKind};
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Minimum [Jaro-Winkler](strsim::jaro_winkler) similarity of a known key to be suggested.
+const SUGGESTION_THRESHOLD: f64 = 0.8;
+
+/// How keys of configuration files that do not match any field are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnknownKeys {
+    /// Unknown keys are ignored.
+    #[default]
+    Ignore,
+    /// Every unknown key emits a [`Warning`].
+    Warn,
+    /// Unknown keys fail the load with [`ConfgrError::UnknownKeys`].
+    Deny,
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(UnknownKeys::Ignore as u8);
+
+/// Sets how unknown keys are treated for configurations without the `strict` attribute, which
+/// always deny them. Unknown keys are ignored by default.
+pub fn set_unknown_keys(policy: UnknownKeys) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Returns the policy set with [`set_unknown_keys()`].
+pub fn unknown_keys() -> UnknownKeys {
+    match POLICY.load(Ordering::Relaxed) {
+        1 => UnknownKeys::Warn,
+        2 => UnknownKeys::Deny,
+        _ => UnknownKeys::Ignore,
+    }
+}
+
+/// A key that does not match any known key, along with the closest known key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    pub key: String,
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown key '{}'", self.key)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean '{}'?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the known key closest to `key`, if any is similar enough.
+pub(crate) fn suggest<'a, I>(key: &str, known: I) -> Option<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    known
+        .into_iter()
+        .map(|candidate| (strsim::jaro_winkler(key, candidate), candidate))
+        .filter(|(similarity, _)| *similarity >= SUGGESTION_THRESHOLD)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Finds the keys of a configuration value tree that are not among the `known` dotted keys.
+///
+/// Values below a known key are not inspected, so that maps and foreign types may hold any key.
+pub fn find_unknown_keys(value: &Value, known: &[String]) -> Vec<UnknownKey> {
+    let mut unknown = Vec::new();
+    if let ValueKind::Table(table) = &value.kind {
+        let mut entries: Vec<_> = table.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        for (key, value) in entries {
+            find_in(value, key, known, &mut unknown);
+        }
+    }
+    unknown
+}
+
+fn find_in(value: &Value, path: &str, known: &[String], unknown: &mut Vec<UnknownKey>) {
+    if known.iter().any(|key| key == path) {
+        return;
+    }
+
+    let section = format!("{}.", path);
+    if known.iter().any(|key| key.starts_with(&section)) {
+        if let ValueKind::Table(table) = &value.kind {
+            let mut entries: Vec<_> = table.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            for (key, value) in entries {
+                find_in(value, &format!("{}{}", section, key), known, unknown);
+            }
+        }
+        return;
+    }
+
+    let (parent, name) = match path.rsplit_once('.') {
+        Some((parent, name)) => (format!("{}.", parent), name),
+        None => (String::new(), path),
+    };
+    let siblings = known.iter().filter_map(|key| {
+        key.strip_prefix(parent.as_str())
+            .map(|key| key.split('.').next().unwrap_or(key))
+    });
+
+    unknown.push(UnknownKey {
+        key: path.to_string(),
+        suggestion: suggest(name, siblings).map(|suggestion| format!("{}{}", parent, suggestion)),
+    });
+}
+
+/// Reports the unknown keys of a configuration value tree according to `policy`.
+pub(crate) fn check_unknown_keys(
+    value: &Value,
+    known: &[String],
+    policy: UnknownKeys,
+) -> Result<(), ConfgrError> {
+    let unknown = find_unknown_keys(value, known);
+
+    match policy {
+        UnknownKeys::Ignore => Ok(()),
+        UnknownKeys::Warn => {
+            for key in unknown {
+                warn(Warning {
+                    field: key.key.clone(),
+                    source: Source::File,
+                    message: key.to_string(),
+                });
+            }
+            Ok(())
+        }
+        UnknownKeys::Deny if unknown.is_empty() => Ok(()),
+        UnknownKeys::Deny => Err(ConfgrError::UnknownKeys(
+            unknown
+                .iter()
+                .map(UnknownKey::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+        )),
+    }
+}
+
+/// Converts a `snake_case` field name as serde's `rename_all` does, e.g. `max_connections` into
+/// `max-connections` for `"kebab-case"`. Unknown rules leave the name unchanged.
+pub fn rename_key(name: &str, rule: &str) -> String {
+    let pascal = || -> String {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    };
+
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => pascal,
+            }
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// Renames the keys of a configuration value tree that are not among the `known` dotted keys to
+/// the known key they match under `rule`, so that nested layers follow the `rename_all` of their
+/// parent unless they set their own. Keys are compared regardless of case, since file sources
+/// lowercase them.
+pub(crate) fn rename_keys(value: Value, known: &[String], rule: Option<&str>) -> Value {
+    rename_in(value, "", known, rule)
+}
+
+fn rename_in(mut value: Value, section: &str, known: &[String], rule: Option<&str>) -> Value {
+    let siblings: Vec<&str> = known
+        .iter()
+        .filter_map(|key| key.strip_prefix(section))
+        .map(|key| key.split('.').next().unwrap_or(key))
+        .collect();
+
+    value.kind = match value.kind {
+        ValueKind::Table(table) => ValueKind::Table(
+            table
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = if siblings.contains(&key.as_str()) {
+                        key
+                    } else {
+                        siblings
+                            .iter()
+                            .find(|sibling| {
+                                sibling.eq_ignore_ascii_case(&key)
+                                    || rule.is_some_and(|rule| {
+                                        rename_key(sibling, rule).eq_ignore_ascii_case(&key)
+                                    })
+                            })
+                            .map_or(key, |sibling| sibling.to_string())
+                    };
+
+                    let path = format!("{}{}.", section, key);
+                    let value = if known.iter().any(|known| known.starts_with(&path)) {
+                        rename_in(value, &path, known, rule)
+                    } else {
+                        value
+                    };
+
+                    (key, value)
+                })
+                .collect(),
+        ),
+        kind => kind,
+    };
+
+    value
+}
+
+/// Whether a configuration value tree sets the given dotted key.
+pub(crate) fn contains_key(value: &Value, key: &str) -> bool {
+    let mut current = value;
+    for segment in key.split('.') {
+        match &current.kind {
+            ValueKind::Table(table) => match table.get(segment) {
+                Some(value) => current = value,
+                None => return false,
+            },
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Moves the keys of flattened fields into a table named after the field, so that the layer can
+/// be deserialized without `#[serde(flatten)]`, which loses the string to number coercion of
+/// [`config`]. Each entry holds the dotted path of a flattened field and the keys it reads, and
+/// outer fields come before the fields flattened inside them.
+pub(crate) fn nest_flattened(mut value: Value, flattened: &[(String, Vec<String>)]) -> Value {
+    for (path, keys) in flattened {
+        let (parent, field) = path.rsplit_once('.').unwrap_or(("", path));
+        let Some(table) = table_mut(&mut value, parent) else {
+            continue;
+        };
+
+        let moved: config::Map<String, Value> = keys
+            .iter()
+            .filter_map(|key| Some((key.clone(), table.remove(key)?)))
+            .collect();
+        if moved.is_empty() {
+            continue;
+        }
+
+        let nested = table
+            .entry(field.to_string())
+            .or_insert_with(|| Value::new(None, ValueKind::Table(config::Map::new())));
+        if let ValueKind::Table(nested) = &mut nested.kind {
+            nested.extend(moved);
+        }
+    }
+
+    value
+}
+
+/// Gets the table at the given dotted path, with an empty path being the root.
+fn table_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut config::Map<String, Value>> {
+    let mut current = value;
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        match &mut current.kind {
+            ValueKind::Table(table) => current = table.get_mut(segment)?,
+            _ => return None,
+        }
+    }
+
+    match &mut current.kind {
+        ValueKind::Table(table) => Some(table),
+        _ => None,
+    }
+}
+
+/// Joins the segments of an override by path into a dotted key, matching each segment to a known
+/// key regardless of case and of `-` or `_`, which environment variable names cannot tell apart.
+pub(crate) fn resolve_path<S: AsRef<str>>(segments: &[S], known: &[String]) -> String {
+    let normalize = |key: &str| key.to_lowercase().replace('-', "_");
+    let mut path = String::new();
+
+    for segment in segments {
+        let segment = segment.as_ref();
+        let section = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{}.", path)
+        };
+        let key = known
+            .iter()
+            .filter_map(|key| key.strip_prefix(section.as_str()))
+            .map(|key| key.split('.').next().unwrap_or(key))
+            .find(|key| normalize(key) == normalize(segment))
+            .map_or_else(|| segment.to_lowercase(), str::to_string);
+
+        path = format!("{}{}", section, key);
+    }
+
+    path
+}
@@ -1,6 +1,31 @@
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::io::Read;
 use thiserror::Error;
 
+mod include;
+mod interpolate;
+mod keys;
+mod paths;
+mod schema;
+pub mod strategy;
+mod warning;
+
+pub use include::{resolve_includes, INCLUDE_KEY};
+pub use interpolate::{interpolate, interpolate_with};
+pub use keys::{
+    find_unknown_keys, rename_key, set_unknown_keys, unknown_keys, UnknownKey, UnknownKeys,
+};
+pub use paths::{dir_files, discover_files, discover_files_with, FILE_EXTENSIONS};
+pub use schema::EnvKey;
+pub use warning::{
+    clear_warning_hook, collect_warnings, set_warning_hook, warn, warn_alias, warn_deprecated,
+    Source, Warning,
+};
+
+/// Supported configuration file formats. Re-export of [`config::FileFormat`].
+pub use config::FileFormat as Format;
+
 /// Shared error type for configuration-related errors.
 #[derive(Error, Debug)]
 pub enum ConfgrError {
@@ -10,11 +35,24 @@ pub enum ConfgrError {
     NoFilePath,
     #[error("Config Error: {0}")]
     Config(#[from] config::ConfigError),
+    #[error("Dotenv Error: {0}")]
+    Dotenv(#[from] dotenvy::Error),
+    #[error("Config file includes itself: {0}")]
+    IncludeCycle(String),
+    #[error("Included config file '{0}' does not exist. Include chain: {1}")]
+    MissingInclude(String, String),
+    #[error("Interpolation Error: {0}")]
+    Interpolation(String),
+    #[error("Unknown configuration keys: {0}")]
+    UnknownKeys(String),
 }
 
 /// Merges configuration layers. Self takes precedence over other.
 pub trait Merge {
     fn merge(self, other: Self) -> Self;
+    /// Sets the fields that are still unset to those of `defaults`, without applying their merge
+    /// strategies, so that default values are only a fallback.
+    fn fill(self, defaults: Self) -> Self;
 }
 
 /// Creates an empty configuration layer, used to initialize all [`None`]'s, instead of [`Default`].
@@ -23,16 +61,376 @@ pub trait Empty {
 }
 
 /// Deserializes a configuration layer from environment variables.
-pub trait FromEnv {
-    fn from_env() -> Self;
-    fn get_env_keys() -> HashMap<String, String>;
+pub trait FromEnv: Sized {
+    /// Resolves each environment key through `lookup`. When `prefix` is given, fields named after
+    /// the struct prefix are read from `{prefix}{FIELD}` instead, as done for flattened fields.
+    fn from_env_prefixed<F>(prefix: Option<&str>, lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>;
+    /// Returns the environment key of each field, named after `prefix` as in
+    /// [`from_env_prefixed()`](FromEnv::from_env_prefixed). Fields with several candidate keys
+    /// report all of them, separated by commas in order of priority.
+    fn get_env_keys_prefixed(prefix: Option<&str>) -> HashMap<String, String>;
+    /// Returns every environment variable read by the layer and its nested layers, including
+    /// aliases, named after `prefix` as in [`from_env_prefixed()`](FromEnv::from_env_prefixed).
+    fn get_env_vars_prefixed(prefix: Option<&str>) -> Vec<String>;
+    /// Returns the prefixes, separator included, of the variables of the layer and its nested
+    /// layers, with `prefix` replacing the struct prefix as in
+    /// [`from_env_prefixed()`](FromEnv::from_env_prefixed).
+    fn get_env_prefixes_prefixed(prefix: Option<&str>) -> Vec<String>;
+    /// Returns the [`EnvKey`] of each field, named after `prefix` as in
+    /// [`from_env_prefixed()`](FromEnv::from_env_prefixed), with default values taken from `defaults`.
+    fn env_schema_prefixed(prefix: Option<&str>, defaults: &Self) -> Vec<EnvKey>;
+
+    /// Resolves each environment key through `lookup` instead of the process environment.
+    fn from_env_with<F>(lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        Self::from_env_prefixed(None, lookup)
+    }
+
+    fn get_env_keys() -> HashMap<String, String> {
+        Self::get_env_keys_prefixed(None)
+    }
+
+    fn get_env_vars() -> Vec<String> {
+        Self::get_env_vars_prefixed(None)
+    }
+
+    fn get_env_prefixes() -> Vec<String> {
+        let mut prefixes = Self::get_env_prefixes_prefixed(None);
+        prefixes.retain(|prefix| !prefix.is_empty());
+        prefixes.sort();
+        prefixes.dedup();
+        prefixes
+    }
+
+    /// Finds the variables among `names` that start with one of the
+    /// [`get_env_prefixes()`](FromEnv::get_env_prefixes) but are not read by the layer, along
+    /// with the closest variable that is.
+    fn find_unknown_env_vars_in<I, S>(names: I) -> Vec<UnknownKey>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let fold = |name: &str| {
+            if Self::env_case_insensitive() {
+                name.to_lowercase()
+            } else {
+                name.to_string()
+            }
+        };
+        let prefixes: Vec<String> = Self::get_env_prefixes().iter().map(|p| fold(p)).collect();
+        let known = Self::get_env_vars();
+        let folded: Vec<String> = known.iter().map(|name| fold(name)).collect();
+
+        let mut unknown: Vec<UnknownKey> = names
+            .into_iter()
+            .map(|name| name.as_ref().to_string())
+            .filter(|name| {
+                let name = fold(name);
+                prefixes
+                    .iter()
+                    .any(|prefix| name.starts_with(prefix.as_str()))
+                    && !folded.contains(&name)
+            })
+            .map(|name| UnknownKey {
+                suggestion: keys::suggest(&name, known.iter().map(String::as_str)),
+                key: name,
+            })
+            .collect();
+        unknown.sort_by(|a, b| a.key.cmp(&b.key));
+
+        unknown
+    }
+
+    /// Scans the process environment for variables as in
+    /// [`find_unknown_env_vars_in()`](FromEnv::find_unknown_env_vars_in).
+    fn find_unknown_env_vars() -> Vec<UnknownKey> {
+        Self::find_unknown_env_vars_in(
+            std::env::vars_os().filter_map(|(name, _)| name.into_string().ok()),
+        )
+    }
+
+    /// Whether [`from_env()`](FromEnv::from_env) and [`from_env_map()`](FromEnv::from_env_map)
+    /// match variables regardless of case, e.g. `app_port` for `APP_PORT`.
+    fn env_case_insensitive() -> bool {
+        false
+    }
+
+    fn from_env() -> Self {
+        if Self::env_case_insensitive() {
+            // The environment is scanned once instead of looking up every key in every case.
+            return Self::from_env_map(&env_vars());
+        }
+
+        Self::from_env_with(|key| std::env::var(key).ok())
+    }
+
+    /// Resolves each environment key from `vars`. Exact matches take precedence over variables
+    /// differing in case, if [`env_case_insensitive()`](FromEnv::env_case_insensitive).
+    fn from_env_map(vars: &HashMap<String, String>) -> Self {
+        if Self::env_case_insensitive() {
+            let folded: HashMap<String, &String> = vars
+                .iter()
+                .map(|(key, value)| (key.to_lowercase(), value))
+                .collect();
+            return Self::from_env_with(|key| {
+                vars.get(key)
+                    .or_else(|| folded.get(&key.to_lowercase()).copied())
+                    .cloned()
+            });
+        }
+
+        Self::from_env_with(|key| vars.get(key).cloned())
+    }
 }
 
-/// Deserializes a configuration layer from a file.
-pub trait FromFile: Sized {
-    fn from_file() -> Result<Self, ConfgrError>;
-    fn check_file() -> Result<(), ConfgrError>;
-    fn get_file_path() -> Option<String>;
+/// Deserializes a configuration layer from one or more files.
+pub trait FromFile: Sized + DeserializeOwned + Merge {
+    fn check_file() -> Result<(), ConfgrError> {
+        check_file_path(Self::get_file_path())
+    }
+
+    fn get_file_path() -> Option<String> {
+        Self::get_file_path_with(process_env)
+    }
+
+    /// Identical to [`get_file_path()`](FromFile::get_file_path), but resolves the `env_path`
+    /// variable through `lookup`.
+    fn get_file_path_with<F>(lookup: F) -> Option<String>
+    where
+        F: Fn(&str) -> Option<String>;
+
+    /// Returns every file that makes up the layer, in ascending order of precedence.
+    fn get_file_paths() -> Vec<String> {
+        Self::get_file_paths_with(process_env)
+    }
+
+    /// Identical to [`get_file_paths()`](FromFile::get_file_paths), but resolves the `env_path`
+    /// variable and the directories searched by `discover` through `lookup`.
+    fn get_file_paths_with<F>(lookup: F) -> Vec<String>
+    where
+        F: Fn(&str) -> Option<String>;
+
+    /// Returns the contents and format of the file baked in with the `embed` attribute, if any.
+    fn get_embedded_file() -> Option<(&'static str, Format)>;
+
+    /// Deserializes and merges every file returned by [`get_file_paths()`](FromFile::get_file_paths).
+    fn from_file() -> Result<Self, ConfgrError> {
+        Self::from_file_with(process_env)
+    }
+
+    /// Identical to [`from_file()`](FromFile::from_file), but resolves the file paths and
+    /// interpolates environment variables through `lookup`.
+    fn from_file_with<F>(lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(file_path) = Self::get_file_path_with(&lookup) {
+            check_file_path(Some(file_path))?;
+        }
+
+        Self::from_paths_with(&Self::get_file_paths_with(&lookup), lookup)
+    }
+
+    /// Deserializes each file and merges them, with later paths taking precedence over earlier ones.
+    fn from_paths(paths: &[String]) -> Result<Self, ConfgrError> {
+        Self::from_paths_with(paths, process_env)
+    }
+
+    /// Identical to [`from_paths()`](FromFile::from_paths), but interpolates environment variables
+    /// resolved through `lookup`.
+    fn from_paths_with<F>(paths: &[String], lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        merge_all(paths.iter().map(|path| Self::from_path_with(path, &lookup)))
+    }
+
+    /// Deserializes a single file, along with the files it includes through the [`INCLUDE_KEY`] if
+    /// the layer [`includes()`](FromFile::includes) other files. The file extension may be omitted.
+    fn from_path(path: &str) -> Result<Self, ConfgrError> {
+        Self::from_path_with(path, process_env)
+    }
+
+    /// Identical to [`from_path()`](FromFile::from_path), but interpolates environment variables
+    /// resolved through `lookup`.
+    fn from_path_with<F>(path: &str, lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let files = if Self::includes() {
+            resolve_includes(path)?
+        } else {
+            vec![path.to_string()]
+        };
+
+        merge_all(files.iter().map(|file| {
+            let config = config::Config::builder()
+                .add_source(config::File::with_name(file))
+                .build()?;
+
+            Self::from_config_with(config, &lookup)
+        }))
+    }
+
+    /// Deserializes the layer embedded at compile time.
+    fn from_embedded() -> Result<Self, ConfgrError> {
+        Self::from_embedded_with(process_env)
+    }
+
+    /// Identical to [`from_embedded()`](FromFile::from_embedded), but interpolates environment
+    /// variables resolved through `lookup`.
+    fn from_embedded_with<F>(lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let (contents, format) = Self::get_embedded_file().ok_or(ConfgrError::NoFilePath)?;
+        Self::from_str_with(contents, format, lookup)
+    }
+
+    /// Deserializes a layer from an already built [`config::Config`]. Every file source goes through this method.
+    fn from_config(config: config::Config) -> Result<Self, ConfgrError> {
+        Self::from_config_with(config, process_env)
+    }
+
+    /// Identical to [`from_config()`](FromFile::from_config), but interpolates environment variables
+    /// resolved through `lookup`.
+    fn from_config_with<F>(config: config::Config, lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        deserialize_config(config, Some(&lookup))
+    }
+
+    /// Identical to [`from_config()`](FromFile::from_config), but never interpolates, for layers
+    /// built from environment variable values such as overrides by path.
+    fn from_env_config(config: config::Config) -> Result<Self, ConfgrError> {
+        deserialize_config(config, None)
+    }
+
+    /// Returns the dotted path of every field, including aliases, used to find unknown keys.
+    fn get_file_keys() -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The serde `rename_all` rule of the layer, if any. File keys of nested layers that do not
+    /// set their own are renamed according to it as well.
+    fn rename_all() -> Option<&'static str> {
+        None
+    }
+
+    /// Whether unknown keys are always denied, regardless of [`set_unknown_keys()`].
+    fn strict() -> bool {
+        false
+    }
+
+    /// Returns the `(key, alias)` pairs of every aliased field as dotted paths, used to warn about
+    /// files that still use an alias.
+    fn get_file_aliases() -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// Returns the dotted path, message and `since` version of every deprecated field, used to warn
+    /// about files that still set them.
+    fn get_file_deprecations() -> Vec<(String, String, Option<String>)> {
+        Vec::new()
+    }
+
+    /// Returns the dotted path of every flattened field along with the top level keys it reads
+    /// from its parent, outer fields first.
+    fn get_flattened() -> Vec<(String, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Whether files may include other files through the top level [`INCLUDE_KEY`], which is then
+    /// reserved and cannot be used as a field name.
+    fn includes() -> bool {
+        false
+    }
+
+    /// Whether `${...}` expressions in file values are resolved with [`interpolate()`] before deserializing.
+    fn interpolates() -> bool {
+        false
+    }
+
+    /// Deserializes a layer from in-memory file contents, such as those embedded with [`include_str!`].
+    fn from_str(contents: &str, format: Format) -> Result<Self, ConfgrError> {
+        Self::from_str_with(contents, format, process_env)
+    }
+
+    /// Identical to [`from_str()`](FromFile::from_str), but interpolates environment variables
+    /// resolved through `lookup`.
+    fn from_str_with<F>(contents: &str, format: Format, lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let config = config::Config::builder()
+            .add_source(config::File::from_str(contents, format))
+            .build()?;
+
+        Self::from_config_with(config, lookup)
+    }
+
+    /// Returns the environment variable set by the `env_json` attribute, if any.
+    fn get_env_json_key() -> Option<&'static str> {
+        None
+    }
+
+    /// Deserializes the layer from the JSON or TOML document held by the
+    /// [`get_env_json_key()`](FromFile::get_env_json_key) variable, resolved through `lookup`.
+    /// Returns `None` if no such variable is configured or set.
+    fn from_env_json_with<F>(lookup: F) -> Result<Option<Self>, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let Some(contents) = Self::get_env_json_key().and_then(&lookup) else {
+            return Ok(None);
+        };
+
+        let format = if contents.trim_start().starts_with('{') {
+            Format::Json
+        } else {
+            Format::Toml
+        };
+
+        let config = config::Config::builder()
+            .add_source(config::File::from_str(&contents, format))
+            .build()?;
+
+        Self::from_env_config(config).map(Some)
+    }
+
+    /// Returns the prefix of variables overriding file keys by path, e.g. `APP__` for
+    /// `APP__SERVICE__URL`, if the struct has a prefix.
+    fn get_env_path_prefix() -> Option<&'static str> {
+        None
+    }
+
+    /// Deserializes a layer from any reader, such as stdin or an archive entry.
+    fn from_reader<R: Read>(mut reader: R, format: Format) -> Result<Self, ConfgrError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+
+        Self::from_str(&contents, format)
+    }
+}
+
+/// Deserializes a configuration layer from a `.env` file without modifying the process environment.
+pub trait FromDotenv: FromEnv {
+    fn get_dotenv_path() -> Option<String>;
+
+    fn from_dotenv() -> Result<Self, ConfgrError> {
+        let dotenv_path = Self::get_dotenv_path().ok_or(ConfgrError::NoFilePath)?;
+        Self::from_dotenv_path(&dotenv_path)
+    }
+
+    fn from_dotenv_path(path: &str) -> Result<Self, ConfgrError> {
+        let vars = dotenvy::from_path_iter(path)?.collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(Self::from_env_map(&vars))
+    }
 }
 
 /// Provides a unified approach to load configurations from environment variables,
@@ -42,27 +440,178 @@ pub trait Confgr
 where
     Self: Sized,
 {
-    type Layer: Default + FromEnv + Merge + FromFile + From<Self> + Into<Self>;
+    type Layer: Default + Empty + FromEnv + FromDotenv + Merge + FromFile + From<Self> + Into<Self>;
 
     /// Loads and merges configurations from files, environment variables, and default values.
-    /// Order of precedence: Environment variables, file configurations, default values.
+    /// Order of precedence: Environment variables, overrides by path such as `APP__SERVICE__URL`,
+    /// `.env` file, `env_json` variable, file configurations, embedded file, default values.
+    ///
+    /// # Panics
+    ///
+    /// Sources that fail to load are skipped, unless unknown keys are denied with the `strict`
+    /// attribute or [`set_unknown_keys()`], in which case this panics rather than dropping a file.
+    /// Use [`try_load_config()`](Confgr::try_load_config) to handle errors instead.
     ///
     /// # Examples
     ///
-    /// ```rust no_run
+    /// ```rust ignore
     /// let config = AppConfig::load_config();
     /// assert_eq!(config.port, 8080);
     /// ```
     fn load_config() -> Self {
-        let file_layer = match Self::deserialize_from_file() {
-            Ok(file_layer) => file_layer,
-            Err(_e) => Self::Layer::default(),
-        };
+        expect_loaded(load_process::<Self>(on_error::<Self>()))
+    }
+
+    /// Identical to [`load_config()`](Confgr::load_config), but returns an error when a source
+    /// cannot be loaded. Sources that are not configured, as well as a
+    /// missing `.env` file or `default_path` file, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if a file, `.env` file or `env_json` variable cannot be
+    /// deserialized, e.g. when it contains unknown keys in `strict` mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let config = AppConfig::try_load_config()?;
+    /// ```
+    fn try_load_config() -> Result<Self, ConfgrError> {
+        load_process::<Self>(OnError::Fail)
+    }
 
-        let default_layer = Self::Layer::default();
-        let env_layer = Self::Layer::from_env();
+    /// Identical to [`load_config()`](Confgr::load_config), but also returns the [`Warning`]s about
+    /// deprecated settings and aliases found while loading.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let (config, warnings) = AppConfig::load_config_with_warnings();
+    /// for warning in warnings {
+    ///     eprintln!("{}", warning);
+    /// }
+    /// ```
+    fn load_config_with_warnings() -> (Self, Vec<Warning>) {
+        collect_warnings(Self::load_config)
+    }
 
-        env_layer.merge(file_layer.merge(default_layer)).into()
+    /// Identical to [`load_config()`](Confgr::load_config), but reads environment variables from
+    /// `vars` instead of the process environment, including those interpolated into files, the
+    /// `env_path` variable and the directories searched by `discover`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source cannot be loaded while unknown keys are denied, as in
+    /// [`load_config()`](Confgr::load_config).
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let vars = HashMap::from([("APP__DATABASE__URL".to_string(), "postgres://".to_string())]);
+    /// let config = AppConfig::load_config_with_env_map(&vars);
+    /// assert_eq!(config.database.url, "postgres://");
+    /// ```
+    fn load_config_with_env_map(vars: &HashMap<String, String>) -> Self {
+        expect_loaded(load_env_map::<Self>(vars, on_error::<Self>()))
+    }
+
+    /// Identical to [`load_config_with_env_map()`](Confgr::load_config_with_env_map), but returns
+    /// an error instead of skipping a source that cannot be loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if a configured source cannot be deserialized.
+    fn try_load_config_with_env_map(vars: &HashMap<String, String>) -> Result<Self, ConfgrError> {
+        load_env_map::<Self>(vars, OnError::Fail)
+    }
+
+    /// Identical to [`load_config()`](Confgr::load_config), but resolves environment variables
+    /// through `lookup` instead of reading the process environment, including those interpolated into
+    /// files, the `env_path` variable and the directories searched by `discover`. Since `lookup`
+    /// cannot list variables, overrides by path such as `APP__SERVICE__URL` are not read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source cannot be loaded while unknown keys are denied, as in
+    /// [`load_config()`](Confgr::load_config).
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let vars = HashMap::from([("APP_PORT".to_string(), "8080".to_string())]);
+    /// let config = AppConfig::load_config_with_env(|key| vars.get(key).cloned());
+    /// assert_eq!(config.port, 8080);
+    /// ```
+    fn load_config_with_env<F>(lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        expect_loaded(load_env::<Self, F>(lookup, on_error::<Self>()))
+    }
+
+    /// Identical to [`load_config_with_env()`](Confgr::load_config_with_env), but returns an error
+    /// instead of skipping a source that cannot be loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if a configured source cannot be deserialized.
+    fn try_load_config_with_env<F>(lookup: F) -> Result<Self, ConfgrError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        load_env::<Self, F>(lookup, OnError::Fail)
+    }
+
+    /// Identical to [`load_config()`](Confgr::load_config), but reads the `.env` layer from the
+    /// provided path instead of the one set by the `dotenv` attribute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a source cannot be loaded while unknown keys are denied, as in
+    /// [`load_config()`](Confgr::load_config).
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let config = AppConfig::load_config_with_dotenv("staging.env");
+    /// ```
+    fn load_config_with_dotenv(path: &str) -> Self {
+        expect_loaded(load_dotenv::<Self>(path, on_error::<Self>()))
+    }
+
+    /// Identical to [`load_config_with_dotenv()`](Confgr::load_config_with_dotenv), but returns an
+    /// error instead of skipping a source that cannot be loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if a configured source cannot be deserialized.
+    fn try_load_config_with_dotenv(path: &str) -> Result<Self, ConfgrError> {
+        load_dotenv::<Self>(path, OnError::Fail)
+    }
+
+    /// Identical to [`load_config()`](Confgr::load_config), but deserializes the file layer from
+    /// in-memory contents instead of the configured file path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if the contents cannot be deserialized in the given [`Format`], or
+    /// another configured source cannot be loaded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let config = AppConfig::load_config_from_str(include_str!("app.toml"), Format::Toml)?;
+    /// ```
+    fn load_config_from_str(contents: &str, format: Format) -> Result<Self, ConfgrError> {
+        load_layers::<Self, _>(
+            OnError::Fail,
+            process_env,
+            Self::Layer::from_str(contents, format),
+            Self::deserialize_from_env_json(),
+            Self::deserialize_from_dotenv(),
+            Self::deserialize_from_env_paths(&env_vars()),
+            Self::Layer::from_env(),
+        )
     }
 
     /// Attempts to deserialize configuration from a file.
@@ -74,7 +623,7 @@ where
     ///
     /// # Examples
     ///
-    /// ``` rust no_run
+    /// ```rust ignore
     /// let file_layer = AppConfig::deserialize_from_file();
     /// match file_layer {
     ///     Ok(layer) => println!("Configuration loaded from file."),
@@ -85,6 +634,116 @@ where
         Self::Layer::from_file()
     }
 
+    /// Attempts to deserialize configuration from the `.env` file set by the `dotenv` attribute.
+    /// The process environment is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if no `.env` file is configured or it cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let dotenv_layer = AppConfig::deserialize_from_dotenv();
+    /// ```
+    fn deserialize_from_dotenv() -> Result<Self::Layer, ConfgrError> {
+        Self::Layer::from_dotenv()
+    }
+
+    /// Attempts to deserialize configuration from the JSON or TOML document in the environment
+    /// variable set by the `env_json` attribute. Returns `None` if the variable is not configured
+    /// or not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if the document cannot be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ``` rust ignore
+    /// if let Err(e) = AppConfig::deserialize_from_env_json() {
+    ///     eprintln!("APP_CONFIG_JSON is invalid: {}", e);
+    /// }
+    /// ```
+    fn deserialize_from_env_json() -> Result<Option<Self::Layer>, ConfgrError> {
+        Self::Layer::from_env_json_with(process_env)
+    }
+
+    /// Attempts to deserialize configuration from the variables of `vars` that start with the
+    /// [`get_env_path_prefix()`](FromFile::get_env_path_prefix), each setting the file key made of
+    /// its lowercased, double-underscore separated segments, e.g. `APP__SERVICE__URL` sets
+    /// `service.url`. The prefix is matched regardless of case with `env_case_insensitive`.
+    /// Returns `None` if no such variable is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if the overridden keys cannot be deserialized, e.g. when a variable
+    /// sets an unknown key in `strict` mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let vars = HashMap::from([("APP__SERVICE__URL".to_string(), "http://localhost".to_string())]);
+    /// let path_layer = AppConfig::deserialize_from_env_paths(&vars)?;
+    /// ```
+    fn deserialize_from_env_paths(
+        vars: &HashMap<String, String>,
+    ) -> Result<Option<Self::Layer>, ConfgrError> {
+        let Some(prefix) = Self::Layer::get_env_path_prefix() else {
+            return Ok(None);
+        };
+
+        let file_keys = Self::Layer::get_file_keys();
+        let case_insensitive = Self::Layer::env_case_insensitive();
+        let fold = |name: &str| {
+            if case_insensitive {
+                name.to_lowercase()
+            } else {
+                name.to_string()
+            }
+        };
+        // Variables of single fields may share the prefix, e.g. with a `__` separator.
+        let field_vars: Vec<String> = Self::Layer::get_env_vars()
+            .iter()
+            .map(|name| fold(name))
+            .collect();
+
+        let mut overrides: Vec<(String, &String)> = vars
+            .iter()
+            .filter(|(key, _)| !field_vars.contains(&fold(key)))
+            .filter_map(|(key, value)| {
+                let path = env_override_path(key, prefix, case_insensitive, &file_keys)?;
+                Some((path, value))
+            })
+            .collect();
+        if overrides.is_empty() {
+            return Ok(None);
+        }
+        overrides.sort();
+
+        let mut builder = config::Config::builder();
+        for (path, value) in overrides {
+            builder = builder.set_override(path, value.as_str())?;
+        }
+
+        Self::Layer::from_env_config(builder.build()?).map(Some)
+    }
+
+    /// Attempts to deserialize the configuration file embedded with the `embed` attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfgrError`] if no file is embedded or it cannot be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let embedded_layer = AppConfig::deserialize_from_embedded();
+    /// ```
+    fn deserialize_from_embedded() -> Result<Self::Layer, ConfgrError> {
+        Self::Layer::from_embedded()
+    }
+
     /// Checks the accessibility of the specified configuration file.
     ///
     /// # Returns
@@ -93,7 +752,7 @@ where
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```rust ignore
     /// if AppConfig::check_file().is_ok() {
     ///     println!("Configuration file is accessible.");
     /// } else {
@@ -112,7 +771,7 @@ where
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```rust ignore
     /// let env_keys = AppConfig::get_env_keys();
     /// assert_eq!(env_keys["port"], "APP_PORT");
     /// ```
@@ -120,6 +779,54 @@ where
         Self::Layer::get_env_keys()
     }
 
+    /// Returns the environment variable, type, default value and doc comment of every field, with
+    /// nested fields holding the keys of their own fields, e.g. to document deployments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// for key in AppConfig::env_schema() {
+    ///     println!("{} = {:?} ({})", key.field_path, key.var_names, key.type_name);
+    /// }
+    /// ```
+    fn env_schema() -> Vec<EnvKey> {
+        Self::Layer::env_schema_prefixed(None, &Self::Layer::default())
+    }
+
+    /// Finds environment variables starting with the configuration's prefix that do not match any
+    /// of its fields, along with the closest match, e.g. to report typos at startup.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// for unknown in AppConfig::find_unknown_env_vars() {
+    ///     eprintln!("{}", unknown);
+    /// }
+    /// ```
+    fn find_unknown_env_vars() -> Vec<UnknownKey> {
+        let mut unknown = Self::Layer::find_unknown_env_vars();
+
+        // Overrides by path are known if they set a file key or an entry below one.
+        if let Some(prefix) = Self::Layer::get_env_path_prefix() {
+            let file_keys = Self::Layer::get_file_keys();
+            unknown.retain(|unknown| {
+                let Some(path) = env_override_path(
+                    &unknown.key,
+                    prefix,
+                    Self::Layer::env_case_insensitive(),
+                    &file_keys,
+                ) else {
+                    return true;
+                };
+                !file_keys
+                    .iter()
+                    .any(|key| path == *key || path.starts_with(&format!("{}.", key)))
+            });
+        }
+
+        unknown
+    }
+
     /// Gets the file path used for loading the configuration, if specified.
     ///
     /// # Returns
@@ -128,7 +835,7 @@ where
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```rust ignore
     /// if let Some(path) = AppConfig::get_file_path() {
     ///     println!("Configuration file used: {}", path);
     /// } else {
@@ -138,4 +845,260 @@ where
     fn get_file_path() -> Option<String> {
         Self::Layer::get_file_path()
     }
+
+    /// Gets every file loaded into the file layer, in ascending order of precedence. This includes
+    /// files found with the `discover` attribute, the resolved file path, and the fragments found
+    /// with the `dir` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// for path in AppConfig::get_file_paths() {
+    ///     println!("Loading configuration from: {}", path);
+    /// }
+    /// ```
+    fn get_file_paths() -> Vec<String> {
+        Self::Layer::get_file_paths()
+    }
+}
+
+/// Resolves an environment variable by name.
+type EnvLookup<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+/// Deserializes a layer from a [`config::Config`], interpolating values through `lookup` if the
+/// layer [`interpolates()`](FromFile::interpolates) and a lookup is given.
+fn deserialize_config<L: FromFile>(
+    config: config::Config,
+    lookup: Option<EnvLookup>,
+) -> Result<L, ConfgrError> {
+    let mut value = config.try_deserialize::<config::Value>()?;
+    let mut file_keys = L::get_file_keys();
+    if L::includes() {
+        file_keys.push(INCLUDE_KEY.to_string());
+    }
+
+    value = keys::rename_keys(value, &file_keys, L::rename_all());
+
+    for (key, alias) in L::get_file_aliases() {
+        if keys::contains_key(&value, &alias) {
+            warn_alias(&key, &alias, Source::File);
+        }
+    }
+
+    for (key, message, since) in L::get_file_deprecations() {
+        if keys::contains_key(&value, &key) {
+            warn_deprecated(&key, &message, since.as_deref(), Source::File);
+        }
+    }
+
+    let policy = if L::strict() {
+        UnknownKeys::Deny
+    } else {
+        unknown_keys()
+    };
+    if policy != UnknownKeys::Ignore {
+        keys::check_unknown_keys(&value, &file_keys, policy)?;
+    }
+
+    if let Some(lookup) = lookup.filter(|_| L::interpolates()) {
+        value = interpolate_with(value, lookup)?;
+    }
+
+    value = keys::nest_flattened(value, &L::get_flattened());
+
+    value.try_deserialize::<L>().map_err(ConfgrError::Config)
+}
+
+/// Merges layers in ascending order of precedence.
+fn merge_all<L: Merge>(
+    mut layers: impl Iterator<Item = Result<L, ConfgrError>>,
+) -> Result<L, ConfgrError> {
+    let first = layers.next().ok_or(ConfgrError::NoFilePath)??;
+
+    layers.try_fold(first, |lower, higher| Ok(higher?.merge(lower)))
+}
+
+/// Reads a variable of the process environment, the default lookup of interpolated files.
+fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Checks that the file at `file_path` exists and can be read.
+fn check_file_path(file_path: Option<String>) -> Result<(), ConfgrError> {
+    let file_path = file_path.ok_or(ConfgrError::NoFilePath)?;
+    std::fs::read_to_string(file_path).map_err(ConfgrError::File)?;
+
+    Ok(())
+}
+
+/// Collects the process environment, skipping variables that are not valid unicode.
+fn env_vars() -> HashMap<String, String> {
+    std::env::vars_os()
+        .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+        .collect()
+}
+
+/// Resolves the file key set by an override by path, or `None` if the variable is not one.
+fn env_override_path(
+    name: &str,
+    prefix: &str,
+    case_insensitive: bool,
+    file_keys: &[String],
+) -> Option<String> {
+    let segments: Vec<&str> = strip_env_path_prefix(name, prefix, case_insensitive)?
+        .split("__")
+        .collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return None;
+    }
+
+    Some(keys::resolve_path(&segments, file_keys))
+}
+
+/// Strips the prefix of an override by path from a variable name, ignoring case if requested.
+fn strip_env_path_prefix<'a>(
+    name: &'a str,
+    prefix: &str,
+    case_insensitive: bool,
+) -> Option<&'a str> {
+    if case_insensitive {
+        name.get(..prefix.len())
+            .filter(|start| start.eq_ignore_ascii_case(prefix))
+            .map(|_| &name[prefix.len()..])
+    } else {
+        name.strip_prefix(prefix)
+    }
+}
+
+/// How the loading methods treat a source that fails to load.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OnError {
+    /// The error is returned.
+    Fail,
+    /// The source is skipped, as if it were not configured.
+    Skip,
+}
+
+impl OnError {
+    /// Replaces the error of a source that is not configured or whose optional file does not
+    /// exist with an empty layer, and that of any other source when skipping errors.
+    fn source<L: Empty>(self, layer: Result<L, ConfgrError>) -> Result<L, ConfgrError> {
+        match layer {
+            Err(ConfgrError::NoFilePath) => Ok(L::empty()),
+            Err(ConfgrError::Dotenv(e)) if e.not_found() => Ok(L::empty()),
+            Err(_) if self == OnError::Skip => Ok(L::empty()),
+            layer => layer,
+        }
+    }
+}
+
+/// The infallible loading methods skip sources that fail to load, unless unknown keys are denied,
+/// in which case they panic rather than silently dropping a file.
+fn on_error<C: Confgr>() -> OnError {
+    if C::Layer::strict() || unknown_keys() == UnknownKeys::Deny {
+        OnError::Fail
+    } else {
+        OnError::Skip
+    }
+}
+
+/// Unwraps a loaded configuration for the infallible loading methods.
+fn expect_loaded<C>(config: Result<C, ConfgrError>) -> C {
+    config.unwrap_or_else(|e| panic!("The configuration could not be loaded: {}", e))
+}
+
+/// Loads from the process environment. Variables of single fields are looked up one by one,
+/// matching their case as the platform does, while the environment is only listed to find
+/// overrides by path.
+fn load_process<C: Confgr>(on_error: OnError) -> Result<C, ConfgrError> {
+    load_layers::<C, _>(
+        on_error,
+        process_env,
+        C::deserialize_from_file(),
+        C::deserialize_from_env_json(),
+        C::deserialize_from_dotenv(),
+        C::deserialize_from_env_paths(&env_vars()),
+        C::Layer::from_env(),
+    )
+}
+
+/// Loads with environment variables read from `vars`.
+fn load_env_map<C: Confgr>(
+    vars: &HashMap<String, String>,
+    on_error: OnError,
+) -> Result<C, ConfgrError> {
+    let lookup = |key: &str| vars.get(key).cloned();
+
+    load_layers::<C, _>(
+        on_error,
+        lookup,
+        C::Layer::from_file_with(lookup),
+        C::Layer::from_env_json_with(lookup),
+        C::deserialize_from_dotenv(),
+        C::deserialize_from_env_paths(vars),
+        C::Layer::from_env_map(vars),
+    )
+}
+
+/// Loads with environment variables resolved through `lookup`, which cannot list overrides by path.
+fn load_env<C: Confgr, F>(lookup: F, on_error: OnError) -> Result<C, ConfgrError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    load_layers::<C, _>(
+        on_error,
+        &lookup,
+        C::Layer::from_file_with(&lookup),
+        C::Layer::from_env_json_with(&lookup),
+        C::deserialize_from_dotenv(),
+        Ok(None),
+        C::Layer::from_env_with(&lookup),
+    )
+}
+
+/// Loads from the process environment with the `.env` layer read from `path`.
+fn load_dotenv<C: Confgr>(path: &str, on_error: OnError) -> Result<C, ConfgrError> {
+    load_layers::<C, _>(
+        on_error,
+        process_env,
+        C::deserialize_from_file(),
+        C::deserialize_from_env_json(),
+        C::Layer::from_dotenv_path(path),
+        C::deserialize_from_env_paths(&env_vars()),
+        C::Layer::from_env(),
+    )
+}
+
+/// Merges the loaded layers, in ascending order of evaluation, on top of the embedded and default
+/// layers and converts the result. The embedded file is interpolated through `lookup`.
+///
+/// # Errors
+///
+/// Returns [`ConfgrError`] if a layer failed to load and errors are not skipped, or if a file
+/// embedded with the `embed` attribute cannot be deserialized.
+fn load_layers<C: Confgr, F>(
+    on_error: OnError,
+    lookup: F,
+    file_layer: Result<C::Layer, ConfgrError>,
+    json_layer: Result<Option<C::Layer>, ConfgrError>,
+    dotenv_layer: Result<C::Layer, ConfgrError>,
+    path_layer: Result<Option<C::Layer>, ConfgrError>,
+    env_layer: C::Layer,
+) -> Result<C, ConfgrError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let optional = |layer: Option<C::Layer>| layer.unwrap_or_else(C::Layer::empty);
+    let file_layer = on_error.source(file_layer)?;
+    let json_layer = on_error.source(json_layer.map(optional))?;
+    let dotenv_layer = on_error.source(dotenv_layer)?;
+    let path_layer = on_error.source(path_layer.map(optional))?;
+    let embedded_layer = OnError::Fail.source(C::Layer::from_embedded_with(lookup))?;
+    let default_layer = C::Layer::default();
+
+    Ok(env_layer
+        .merge(path_layer)
+        .merge(dotenv_layer.merge(json_layer.merge(file_layer.merge(embedded_layer))))
+        .fill(default_layer)
+        .into())
 }
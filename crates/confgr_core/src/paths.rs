@@ -0,0 +1,75 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File extensions recognized when searching for configuration files, in lookup order.
+pub const FILE_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "json", "json5", "ini", "ron"];
+
+/// Searches the standard configuration directories for files belonging to the application `name`.
+///
+/// The following locations are searched, and existing files are returned in ascending order of precedence:
+/// 1. `/etc/{name}/config.*`
+/// 2. `$XDG_CONFIG_HOME/{name}/config.*`, falling back to `~/.config/{name}/config.*` when unset
+/// 3. `./{name}.*`
+pub fn discover_files(name: &str) -> Vec<String> {
+    discover_files_with(name, |key| env::var(key).ok())
+}
+
+/// Identical to [`discover_files()`], but resolves `XDG_CONFIG_HOME` and `HOME` through `lookup`.
+pub fn discover_files_with<F>(name: &str, lookup: F) -> Vec<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut candidates = vec![Path::new("/etc").join(name).join("config")];
+
+    let config_home = lookup("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| lookup("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    if let Some(config_home) = config_home {
+        candidates.push(config_home.join(name).join("config"));
+    }
+
+    candidates.push(PathBuf::from(name));
+
+    let mut files: Vec<String> = Vec::new();
+
+    for candidate in candidates {
+        for extension in FILE_EXTENSIONS {
+            let file = format!("{}.{}", candidate.display(), extension);
+
+            if Path::new(&file).is_file() && !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+
+    files
+}
+
+/// Lists the configuration fragments in `dir` with a recognized extension, sorted by file name.
+/// Returns an empty list if the directory does not exist.
+pub fn dir_files(dir: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| FILE_EXTENSIONS.contains(&extension))
+        })
+        .collect();
+
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+    files
+        .into_iter()
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect()
+}
@@ -0,0 +1,34 @@
+/// The environment variable of a field along with its metadata, as returned by
+/// [`Confgr::env_schema()`](crate::Confgr::env_schema).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvKey {
+    /// The dotted path of the field, e.g. `database.url`.
+    pub field_path: String,
+    /// The variables the field is read from, in the order they are tried. Empty for nested fields
+    /// and fields which are not read from environment variables.
+    pub var_names: Vec<String>,
+    /// The type of the field as written in the struct.
+    pub type_name: &'static str,
+    /// Whether the field is never read from environment variables.
+    pub skipped: bool,
+    /// The [`Debug`] representation of the field's default value, if it has one. Always `None` for
+    /// fields whose type mentions a type parameter, which need not implement [`Debug`].
+    pub default_repr: Option<String>,
+    /// The doc comment of the field.
+    pub doc: Option<String>,
+    /// The keys of a nested field.
+    pub children: Vec<EnvKey>,
+}
+
+impl EnvKey {
+    /// Prepends `parent` to the field path of the key and its children, for keys of nested fields.
+    pub fn with_parent(mut self, parent: &str) -> Self {
+        self.field_path = format!("{}.{}", parent, self.field_path);
+        self.children = self
+            .children
+            .into_iter()
+            .map(|child| child.with_parent(parent))
+            .collect();
+        self
+    }
+}
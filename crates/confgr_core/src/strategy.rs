@@ -0,0 +1,33 @@
+//! Built-in field merge strategies, selected with `#[config(merge = "...")]`.
+//!
+//! Every strategy receives the value of the higher precedence layer first. Default values are not
+//! merged, and only used for fields which no other layer sets.
+
+/// Keeps the higher precedence value. This is the default strategy.
+pub fn replace<T>(higher: Option<T>, lower: Option<T>) -> Option<T> {
+    higher.or(lower)
+}
+
+/// Appends the items of the higher precedence collection to the lower precedence collection.
+pub fn append<C, T>(higher: Option<C>, lower: Option<C>) -> Option<C>
+where
+    C: IntoIterator<Item = T> + Extend<T>,
+{
+    match (higher, lower) {
+        (Some(higher), Some(mut lower)) => {
+            lower.extend(higher);
+            Some(lower)
+        }
+        (higher, lower) => higher.or(lower),
+    }
+}
+
+/// Merges two maps key by key, with the higher precedence map winning on conflicting keys.
+///
+/// Values under a conflicting key are replaced as a whole, so nested maps are not merged.
+pub fn union<M, K, V>(higher: Option<M>, lower: Option<M>) -> Option<M>
+where
+    M: IntoIterator<Item = (K, V)> + Extend<(K, V)>,
+{
+    append(higher, lower)
+}
@@ -0,0 +1,120 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::RwLock;
+
+/// The kind of source a setting was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// An environment variable, including those read from a dotenv file.
+    Env,
+    /// A configuration file, embedded file or in-memory contents.
+    File,
+}
+
+/// A warning about a deprecated setting found while loading a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The current name of the setting, i.e. its environment variable or its dotted file key.
+    pub field: String,
+    pub source: Source,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:?}): {}", self.field, self.source, self.message)
+    }
+}
+
+type Hook = Box<dyn Fn(&Warning) + Send + Sync>;
+
+static HOOK: RwLock<Option<Hook>> = RwLock::new(None);
+
+thread_local! {
+    static COLLECTED: RefCell<Option<Vec<Warning>>> = const { RefCell::new(None) };
+}
+
+/// Registers a function called with every [`Warning`] emitted while loading configurations,
+/// replacing any previous hook. Warnings are discarded if no hook is set.
+pub fn set_warning_hook<H>(hook: H)
+where
+    H: Fn(&Warning) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(hook));
+}
+
+/// Removes the hook registered with [`set_warning_hook()`].
+pub fn clear_warning_hook() {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Runs `f` and returns its result along with every [`Warning`] emitted on this thread meanwhile.
+pub fn collect_warnings<T, F>(f: F) -> (T, Vec<Warning>)
+where
+    F: FnOnce() -> T,
+{
+    let outer = COLLECTED.with(|collected| collected.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let warnings = COLLECTED.with(|collected| {
+        let warnings = collected.borrow_mut().take().unwrap_or_default();
+        *collected.borrow_mut() = outer.map(|mut outer| {
+            outer.extend(warnings.iter().cloned());
+            outer
+        });
+        warnings
+    });
+
+    (result, warnings)
+}
+
+/// Emits a warning through the registered hook, to [`collect_warnings()`] and, with the `log` or
+/// `tracing` features, as a log event. Used by the generated code.
+#[doc(hidden)]
+pub fn warn(warning: Warning) {
+    #[cfg(feature = "log")]
+    log::warn!(target: "confgr", "{}", warning);
+
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        target: "confgr",
+        field = %warning.field,
+        source = ?warning.source,
+        "{}",
+        warning.message
+    );
+
+    if let Some(hook) = HOOK.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        hook(&warning);
+    }
+
+    COLLECTED.with(|collected| {
+        if let Some(collected) = collected.borrow_mut().as_mut() {
+            collected.push(warning);
+        }
+    });
+}
+
+/// Emits a warning for a deprecated setting that is set. Used by the generated code.
+#[doc(hidden)]
+pub fn warn_deprecated(field: &str, message: &str, since: Option<&str>, source: Source) {
+    let message = match since {
+        Some(since) => format!("deprecated since {}: {}", since, message),
+        None => format!("deprecated: {}", message),
+    };
+
+    warn(Warning {
+        field: field.to_string(),
+        source,
+        message,
+    });
+}
+
+/// Emits a warning for a setting found under one of its aliases. Used by the generated code.
+#[doc(hidden)]
+pub fn warn_alias(field: &str, alias: &str, source: Source) {
+    warn(Warning {
+        field: field.to_string(),
+        source,
+        message: format!("'{}' is deprecated, use '{}' instead", alias, field),
+    });
+}
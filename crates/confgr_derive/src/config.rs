@@ -1,15 +1,30 @@
-use crate::SUFFIX;
+use crate::{add_predicates, SUFFIX};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::Ident;
+use syn::{parse_quote, Generics, Ident};
 
-pub fn generate_config_impl(name: &Ident) -> TokenStream {
+pub fn generate_config_impl(name: &Ident, generics: &Generics) -> TokenStream {
     let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let generics = add_predicates(
+        generics,
+        [parse_quote! {
+            #layer_name #ty_generics: Default
+                + ::confgr::core::Empty
+                + ::confgr::core::FromEnv
+                + ::confgr::core::FromDotenv
+                + ::confgr::core::Merge
+                + ::confgr::core::FromFile
+                + From<Self>
+                + Into<Self>
+        }],
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     quote! {
         #[automatically_derived]
-        impl ::confgr::core::Confgr for #name {
-            type Layer = #layer_name;
+        impl #impl_generics ::confgr::core::Confgr for #name #ty_generics #where_clause {
+            type Layer = #layer_name #ty_generics;
         }
     }
 }
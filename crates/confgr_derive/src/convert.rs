@@ -1,38 +1,40 @@
 #![allow(non_snake_case)]
-use crate::{get_ident_from_type, ConfigAttributes, SUFFIX};
+use crate::{add_predicates, nested_layer, ConfigAttributes, SUFFIX};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Ident, Type};
+use syn::{parse_quote, Generics, Ident, Member, Type};
 
 pub fn generate_conversion_impl(
     name: &Ident,
-    field_data: &[(&Ident, &Type, ConfigAttributes)],
+    generics: &Generics,
+    field_data: &[(Member, &Type, ConfigAttributes)],
 ) -> TokenStream {
     let BASE_PARAMETER: Ident = format_ident!("{}", "base");
     let LAYER_PARAMETER: Ident = format_ident!("{}", "layer");
 
     let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let default_generics = add_predicates(generics, [parse_quote! { Self: Default }]);
+    let (_, _, default_where_clause) = default_generics.split_for_impl();
 
     let from_layer_conversions = field_data.iter().map(|(field_name, ty, attr)| {
         if attr.nest {
-            let nested_type = get_ident_from_type(ty);
-            let nested_layer_name = format_ident!("{}{}", nested_type, SUFFIX);
+            let nested_layer_name = nested_layer(ty);
             quote! {
-                #field_name: <#nested_type as ::core::convert::From<#nested_layer_name>>::from(#LAYER_PARAMETER.#field_name),
+                #field_name: <#nested_layer_name as ::core::convert::Into<#ty>>::into(#LAYER_PARAMETER.#field_name),
             }
         } else {
             quote! {
-                #field_name: #LAYER_PARAMETER.#field_name.unwrap_or_else(|| #name::default().#field_name),
+                #field_name: #LAYER_PARAMETER.#field_name.unwrap_or_else(|| <Self as Default>::default().#field_name),
             }
         }
     });
 
     let from_base_conversions = field_data.iter().map(|(field_name, ty, attr)| {
         if attr.nest {
-            let nested_type = get_ident_from_type(ty);
-            let nested_layer_name = format_ident!("{}{}", nested_type, SUFFIX);
+            let nested_layer_name = nested_layer(ty);
             quote! {
-                #field_name: <#nested_layer_name as ::core::convert::From<#nested_type>>::from(#BASE_PARAMETER.#field_name),
+                #field_name: <#nested_layer_name as ::core::convert::From<#ty>>::from(#BASE_PARAMETER.#field_name),
             }
         } else {
             quote! {
@@ -43,8 +45,8 @@ pub fn generate_conversion_impl(
 
     quote! {
         #[automatically_derived]
-        impl ::core::convert::From<#layer_name> for #name {
-            fn from(#LAYER_PARAMETER: #layer_name) -> Self {
+        impl #impl_generics ::core::convert::From<#layer_name #ty_generics> for #name #ty_generics #default_where_clause {
+            fn from(#LAYER_PARAMETER: #layer_name #ty_generics) -> Self {
                 Self {
                     #( #from_layer_conversions )*
                 }
@@ -52,8 +54,8 @@ pub fn generate_conversion_impl(
         }
 
         #[automatically_derived]
-        impl ::core::convert::From<#name> for #layer_name {
-            fn from(#BASE_PARAMETER: #name) -> Self {
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #layer_name #ty_generics #where_clause {
+            fn from(#BASE_PARAMETER: #name #ty_generics) -> Self {
                 Self {
                     #( #from_base_conversions )*
                 }
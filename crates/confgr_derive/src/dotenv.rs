@@ -0,0 +1,37 @@
+use crate::{add_predicates, ConfigAttributes, SUFFIX};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Generics, Ident};
+
+pub(crate) fn generate_from_dotenv(
+    name: &Ident,
+    generics: &Generics,
+    attributes: &ConfigAttributes,
+) -> TokenStream {
+    let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let generics = add_predicates(generics, [parse_quote! { Self: ::confgr::core::FromEnv }]);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let get_dotenv_path_def = if let Some(dotenv_path) = &attributes.dotenv {
+        quote! {
+            fn get_dotenv_path() -> Option<String> {
+                if std::path::Path::new(#dotenv_path).exists() {
+                    Some(#dotenv_path.to_string())
+                } else {
+                    None
+                }
+            }
+        }
+    } else {
+        quote! {
+            fn get_dotenv_path() -> Option<String> { None }
+        }
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::confgr::core::FromDotenv for #layer_name #ty_generics #where_clause {
+            #get_dotenv_path_def
+        }
+    }
+}
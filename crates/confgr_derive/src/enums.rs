@@ -0,0 +1,490 @@
+use crate::{
+    env, extract_fields, member_name, merge, nested_layer, parse_config_field_attributes,
+    to_snake_case, ConfigAttributes, SUFFIX,
+};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{spanned::Spanned, DataEnum, Fields, Generics, Ident, Member, Type};
+
+const DEFAULT_TAG: &str = "kind";
+
+struct VariantData<'a> {
+    ident: &'a Ident,
+    tag: String,
+    /// The field of the enum layer holding the variant's layer, `None` for unit variants.
+    layer: Option<(Ident, Ident)>,
+    field_data: Vec<(Member, &'a Type, ConfigAttributes)>,
+}
+
+/// Generates the layer of an enum, along with its conversions and environment variable support.
+///
+/// The layer records the selected variant under the tag key, e.g. `kind = "local"`, and keeps a
+/// separate layer for the fields of every variant, so that a higher layer may switch variants.
+pub fn generate_enum(
+    name: &Ident,
+    enum_attributes: &ConfigAttributes,
+    data: &DataEnum,
+) -> Result<(TokenStream, TokenStream, TokenStream), Vec<syn::Error>> {
+    let variants = extract_variants(name, enum_attributes, data)?;
+    let tag_key = tag_key(enum_attributes);
+
+    Ok((
+        generate_enum_layer(name, enum_attributes, tag_key, &variants),
+        generate_enum_conversions(name, &variants),
+        generate_enum_from_env(name, enum_attributes, tag_key, &variants),
+    ))
+}
+
+/// The key selecting the variant of an enum, in files and environment variables.
+pub(crate) fn tag_key(enum_attributes: &ConfigAttributes) -> &str {
+    enum_attributes.tag.as_deref().unwrap_or(DEFAULT_TAG)
+}
+
+fn extract_variants<'a>(
+    name: &Ident,
+    enum_attributes: &ConfigAttributes,
+    data: &'a DataEnum,
+) -> Result<Vec<VariantData<'a>>, Vec<syn::Error>> {
+    let tag_key = tag_key(enum_attributes);
+    let mut variants = Vec::new();
+    let mut errors = Vec::new();
+
+    if data.variants.is_empty() {
+        errors.push(syn::Error::new_spanned(
+            name,
+            "Config derive macro requires at least one enum variant.",
+        ));
+    }
+
+    for variant in data.variants.iter() {
+        let attributes = match parse_config_field_attributes(&variant.attrs) {
+            Ok(attributes) => attributes,
+            Err(mut errs) => {
+                errors.append(&mut errs);
+                continue;
+            }
+        };
+
+        let field_data = match &variant.fields {
+            Fields::Unnamed(_) => {
+                errors.push(syn::Error::new_spanned(
+                    variant,
+                    "Config derive macro does not support tuple variants, use named fields instead.",
+                ));
+                continue;
+            }
+            fields => match extract_fields(fields) {
+                Ok(field_data) => field_data,
+                Err(mut errs) => {
+                    errors.append(&mut errs);
+                    continue;
+                }
+            },
+        };
+
+        let snake_name = to_snake_case(&variant.ident.to_string());
+
+        for (field_name, _, attr) in &field_data {
+            if attr.name.clone().unwrap_or_else(|| member_name(field_name)) == tag_key {
+                errors.push(syn::Error::new_spanned(
+                    field_name,
+                    format!(
+                        "Field conflicts with the tag key '{}', see the 'tag' attribute.",
+                        tag_key
+                    ),
+                ));
+            }
+        }
+
+        let layer = if let Fields::Named(_) = &variant.fields {
+            if snake_name == "tag" {
+                errors.push(syn::Error::new_spanned(
+                    variant,
+                    "A variant with fields cannot be named 'Tag'.",
+                ));
+            }
+            let field = syn::parse_str::<Ident>(&snake_name)
+                .unwrap_or_else(|_| Ident::new_raw(&snake_name, variant.ident.span()));
+            let layer_name = format_ident!("{}{}{}", name, variant.ident, SUFFIX);
+            Some((field, layer_name))
+        } else {
+            None
+        };
+
+        variants.push(VariantData {
+            ident: &variant.ident,
+            tag: attributes.name.unwrap_or(snake_name),
+            layer,
+            field_data,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(variants)
+    } else {
+        Err(errors)
+    }
+}
+
+fn generate_enum_layer(
+    name: &Ident,
+    enum_attributes: &ConfigAttributes,
+    tag_key: &str,
+    variants: &[VariantData],
+) -> TokenStream {
+    let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let rename_all = if let Some(rule) = &enum_attributes.rename_all {
+        quote! { #[serde(rename_all = #rule)] }
+    } else {
+        quote! {}
+    };
+    let tags = variants.iter().map(|variant| &variant.tag);
+
+    let variant_layers = variants.iter().filter_map(|variant| {
+        let (_, variant_layer) = variant.layer.as_ref()?;
+        let (field_defs, empty_defs, merges, fills) =
+            merge::generate_layer_fields(&variant.field_data);
+
+        Some(quote! {
+            #[automatically_derived]
+            #[derive(::serde::Deserialize, Debug, Clone)]
+            #[doc(hidden)]
+            #rename_all
+            pub struct #variant_layer {
+                #( #field_defs ),*
+            }
+
+            #[automatically_derived]
+            impl ::confgr::core::Merge for #variant_layer {
+                fn merge(self, other: Self) -> Self {
+                    Self {
+                        #( #merges ),*
+                    }
+                }
+
+                fn fill(self, defaults: Self) -> Self {
+                    Self {
+                        #( #fills ),*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl ::confgr::core::Empty for #variant_layer {
+                fn empty() -> Self {
+                    Self {
+                        #( #empty_defs ),*
+                    }
+                }
+            }
+        })
+    });
+
+    let fields: Vec<_> = variants
+        .iter()
+        .filter_map(|variant| variant.layer.as_ref())
+        .collect();
+    let field_defs = fields
+        .iter()
+        .map(|(field, variant_layer)| quote! { pub #field: #variant_layer });
+    let empty_defs = fields.iter().map(|(field, variant_layer)| {
+        quote! { #field: <#variant_layer as ::confgr::core::Empty>::empty() }
+    });
+    let merges = fields.iter().map(|(field, _)| {
+        quote! { #field: ::confgr::core::Merge::merge(self.#field, other.#field) }
+    });
+    let fills = fields.iter().map(|(field, _)| {
+        quote! { #field: ::confgr::core::Merge::fill(self.#field, defaults.#field) }
+    });
+    let deserialized = variants.iter().filter_map(|variant| {
+        let (field, variant_layer) = variant.layer.as_ref()?;
+        let tag = &variant.tag;
+        // Fields of variants other than the selected one may not fit the table, and are ignored.
+        Some(quote! {
+            #field: match <#variant_layer as ::serde::Deserialize>::deserialize(value.clone()) {
+                Ok(layer) => layer,
+                Err(_) if tag.as_deref().is_some_and(|tag| tag != #tag) => {
+                    <#variant_layer as ::confgr::core::Empty>::empty()
+                }
+                Err(error) => return Err(::serde::de::Error::custom(error)),
+            }
+        })
+    });
+
+    quote! {
+        #( #variant_layers )*
+
+        #[automatically_derived]
+        #[derive(Debug, Clone)]
+        #[doc(hidden)]
+        pub struct #layer_name {
+            pub tag: Option<String>,
+            #( #field_defs ),*
+        }
+
+        #[automatically_derived]
+        impl<'de> ::serde::Deserialize<'de> for #layer_name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                const TAGS: &[&str] = &[#( #tags ),*];
+
+                let value = <::confgr::config::Value as ::serde::Deserialize>::deserialize(deserializer)?;
+                // A plain string selects a variant without setting any of its fields.
+                let (tag, value) = match value.kind {
+                    ::confgr::config::ValueKind::Table(mut table) => {
+                        let tag = table
+                            .remove(#tag_key)
+                            .map(|tag| tag.into_string())
+                            .transpose()
+                            .map_err(::serde::de::Error::custom)?;
+                        let table = ::confgr::config::ValueKind::Table(table);
+                        (tag, ::confgr::config::Value::new(None, table))
+                    }
+                    kind => {
+                        let tag = ::confgr::config::Value::new(None, kind)
+                            .into_string()
+                            .map_err(::serde::de::Error::custom)?;
+                        let table = ::confgr::config::ValueKind::Table(Default::default());
+                        (Some(tag), ::confgr::config::Value::new(None, table))
+                    }
+                };
+
+                let tag = match tag {
+                    Some(tag) => match TAGS.iter().find(|known| known.eq_ignore_ascii_case(&tag)) {
+                        Some(known) => Some(known.to_string()),
+                        None => return Err(::serde::de::Error::unknown_variant(&tag, TAGS)),
+                    },
+                    None => None,
+                };
+
+                Ok(Self {
+                    #( #deserialized, )*
+                    tag,
+                })
+            }
+        }
+
+        #[automatically_derived]
+        impl Default for #layer_name {
+            fn default() -> Self {
+                #name::default().into()
+            }
+        }
+
+        #[automatically_derived]
+        impl ::confgr::core::Merge for #layer_name {
+            fn merge(self, other: Self) -> Self {
+                Self {
+                    tag: self.tag.or(other.tag),
+                    #( #merges ),*
+                }
+            }
+
+            fn fill(self, defaults: Self) -> Self {
+                Self {
+                    tag: self.tag.or(defaults.tag),
+                    #( #fills ),*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::confgr::core::Empty for #layer_name {
+            fn empty() -> Self {
+                Self {
+                    tag: None,
+                    #( #empty_defs ),*
+                }
+            }
+        }
+    }
+}
+
+fn generate_enum_conversions(name: &Ident, variants: &[VariantData]) -> TokenStream {
+    let layer_name = format_ident!("{}{}", name, SUFFIX);
+
+    let from_layer_arms = variants.iter().map(|variant| {
+        let ident = variant.ident;
+        let tag = &variant.tag;
+        match &variant.layer {
+            Some((field, _)) => {
+                let conversions = variant.field_data.iter().map(|(field_name, ty, attr)| {
+                    if attr.nest {
+                        let nested_layer_name = nested_layer(ty);
+                        quote! {
+                            #field_name: <#nested_layer_name as ::core::convert::Into<#ty>>::into(layer.#field.#field_name)
+                        }
+                    } else {
+                        // Spanned to the field, which must implement `Default` for variants selected
+                        // without setting it.
+                        let default = quote_spanned! {ty.span()=>
+                            <#ty as ::core::default::Default>::default
+                        };
+                        quote! { #field_name: layer.#field.#field_name.unwrap_or_else(#default) }
+                    }
+                });
+                quote! { Some(#tag) => #name::#ident { #( #conversions ),* } }
+            }
+            None => quote! { Some(#tag) => #name::#ident },
+        }
+    });
+
+    let from_base_arms = variants.iter().map(|variant| {
+        let ident = variant.ident;
+        let tag = &variant.tag;
+        match &variant.layer {
+            Some((field, variant_layer)) => {
+                let bindings = variant.field_data.iter().map(|(field_name, _, _)| field_name);
+                let conversions = variant.field_data.iter().map(|(field_name, ty, attr)| {
+                    if attr.nest {
+                        let nested_layer_name = nested_layer(ty);
+                        quote! {
+                            #field_name: <#nested_layer_name as ::core::convert::From<#ty>>::from(#field_name)
+                        }
+                    } else {
+                        quote! { #field_name: Some(#field_name) }
+                    }
+                });
+                quote! {
+                    #name::#ident { #( #bindings ),* } => {
+                        layer.tag = Some(#tag.to_string());
+                        layer.#field = #variant_layer { #( #conversions ),* };
+                    }
+                }
+            }
+            None => quote! {
+                #name::#ident => layer.tag = Some(#tag.to_string()),
+            },
+        }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl ::core::convert::From<#layer_name> for #name {
+            fn from(layer: #layer_name) -> Self {
+                match layer.tag.as_deref() {
+                    #( #from_layer_arms, )*
+                    _ => #name::default(),
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::From<#name> for #layer_name {
+            fn from(base: #name) -> Self {
+                let mut layer = <#layer_name as ::confgr::core::Empty>::empty();
+                match base {
+                    #( #from_base_arms )*
+                }
+                layer
+            }
+        }
+    }
+}
+
+fn generate_enum_from_env(
+    name: &Ident,
+    enum_attributes: &ConfigAttributes,
+    tag_key: &str,
+    variants: &[VariantData],
+) -> TokenStream {
+    let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let tags = variants.iter().map(|variant| &variant.tag);
+    let tag_var = env::env_var_expr(tag_key, enum_attributes, &ConfigAttributes::default());
+
+    let tag_item = if enum_attributes.skip {
+        quote! { tag: None }
+    } else {
+        quote! {
+            tag: lookup(&#tag_var).and_then(|tag| {
+                [#( #tags ),*]
+                    .iter()
+                    .find(|known| known.eq_ignore_ascii_case(&tag))
+                    .map(|known| known.to_string())
+            })
+        }
+    };
+
+    let mut env_keys = vec![quote! { map.insert(#tag_key.to_string(), #tag_var); }];
+    let mut env_vars = vec![quote! { vars.push(#tag_var); }];
+    env_vars.extend(env::generate_env_json_var(enum_attributes));
+    let mut env_prefixes =
+        env::generate_env_fields(enum_attributes, &Generics::default(), &[], quote! {}).prefixes;
+    let tag_skipped = enum_attributes.skip;
+    let mut env_schema = vec![quote! {
+        schema.push(::confgr::core::EnvKey {
+            field_path: #tag_key.to_string(),
+            var_names: if #tag_skipped { Vec::new() } else { vec![#tag_var] },
+            type_name: "String",
+            skipped: #tag_skipped,
+            default_repr: defaults.tag.as_ref().map(|tag| format!("{:?}", tag)),
+            doc: None,
+            children: Vec::new(),
+        });
+    }];
+    let env_items = variants.iter().filter_map(|variant| {
+        let (field, variant_layer) = variant.layer.as_ref()?;
+        let mut fields = env::generate_env_fields(
+            enum_attributes,
+            &Generics::default(),
+            &variant.field_data,
+            quote! { defaults.#field },
+        );
+        env_schema.append(&mut fields.schema);
+        env_keys.append(&mut fields.keys);
+        env_vars.append(&mut fields.vars);
+        env_prefixes.append(&mut fields.prefixes);
+        let items = fields.items;
+        Some(quote! { #field: #variant_layer { #( #items ),* } })
+    });
+    let env_items: Vec<_> = env_items.collect();
+    let env_case_insensitive_def = env::generate_env_case_insensitive(enum_attributes);
+
+    quote! {
+        #[automatically_derived]
+        impl ::confgr::core::FromEnv for #layer_name {
+            #[allow(unused_variables)]
+            fn from_env_prefixed<F>(prefix: Option<&str>, lookup: F) -> Self
+            where
+                F: Fn(&str) -> Option<String>,
+            {
+                Self {
+                    #tag_item,
+                    #( #env_items ),*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn get_env_keys_prefixed(prefix: Option<&str>) -> ::std::collections::HashMap<String, String> {
+                let mut map = ::std::collections::HashMap::new();
+                #( #env_keys )*
+                map
+            }
+
+            #[allow(unused_variables)]
+            fn get_env_vars_prefixed(prefix: Option<&str>) -> Vec<String> {
+                let mut vars = Vec::new();
+                #( #env_vars )*
+                vars
+            }
+
+            #[allow(unused_variables)]
+            fn get_env_prefixes_prefixed(prefix: Option<&str>) -> Vec<String> {
+                let mut prefixes = Vec::new();
+                #( #env_prefixes )*
+                prefixes
+            }
+
+            #[allow(unused_variables)]
+            fn env_schema_prefixed(prefix: Option<&str>, defaults: &Self) -> Vec<::confgr::core::EnvKey> {
+                let mut schema = Vec::new();
+                #( #env_schema )*
+                schema
+            }
+
+            #env_case_insensitive_def
+        }
+    }
+}
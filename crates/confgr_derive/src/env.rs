@@ -1,124 +1,556 @@
-use crate::{get_ident_from_type, ConfigAttributes, SUFFIX};
+use crate::{add_predicates, member_name, nested_layer, ConfigAttributes, SUFFIX};
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
-use syn::{Ident, Type};
+use proc_macro2::TokenTree;
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, Generics, Ident, Member, Type};
 
 const DEFAULT_PREFIX: &str = "";
 const DEFAULT_SEPARATOR: &str = "_";
 
+/// Supported values of the `env_case` attribute.
+pub(crate) const ENV_CASES: [&str; 4] = ["SCREAMING_SNAKE", "camel", "kebab", "preserve"];
+const DEFAULT_ENV_CASE: &str = "SCREAMING_SNAKE";
+
+/// The `env_case` of a field, falling back to the struct's and then to `SCREAMING_SNAKE`.
+fn env_case<'a>(struct_attributes: &'a ConfigAttributes, attr: &'a ConfigAttributes) -> &'a str {
+    attr.env_case
+        .as_deref()
+        .or(struct_attributes.env_case.as_deref())
+        .unwrap_or(DEFAULT_ENV_CASE)
+}
+
+/// Applies the `env_case` to a name given verbatim, i.e. a `prefix`, `key` or `env_alias`, which
+/// are only uppercased by `SCREAMING_SNAKE` and otherwise used as written.
+pub(crate) fn env_case_name(
+    name: &str,
+    struct_attributes: &ConfigAttributes,
+    attr: &ConfigAttributes,
+) -> String {
+    match env_case(struct_attributes, attr) {
+        DEFAULT_ENV_CASE => name.to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// Converts a field name to the `env_case` of the field. The words of the name are joined by the
+/// `separator` with `separate_words`, except in `camel` case, which does not delimit words.
+pub(crate) fn env_field_name(
+    field_name: &str,
+    struct_attributes: &ConfigAttributes,
+    attr: &ConfigAttributes,
+) -> String {
+    let case = env_case(struct_attributes, attr);
+    let word_separator = if attr.separate_words || struct_attributes.separate_words {
+        separator(struct_attributes, attr)
+    } else if case == "kebab" {
+        "-"
+    } else {
+        "_"
+    };
+
+    match case {
+        "camel" => field_name
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .enumerate()
+            .map(|(i, word)| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) if i > 0 => first.to_uppercase().chain(chars).collect(),
+                    _ => word.to_string(),
+                }
+            })
+            .collect(),
+        "kebab" => field_name.to_lowercase().replace('_', word_separator),
+        "preserve" => field_name.replace('_', word_separator),
+        _ => field_name.to_uppercase().replace('_', word_separator),
+    }
+}
+
+fn separator<'a>(struct_attributes: &'a ConfigAttributes, attr: &'a ConfigAttributes) -> &'a str {
+    attr.separator
+        .as_deref()
+        .or(struct_attributes.separator.as_deref())
+        .unwrap_or(DEFAULT_SEPARATOR)
+}
+
+/// Separates the segments of variables overriding file keys by path, e.g. `APP__SERVICE__URL`.
+const PATH_SEPARATOR: &str = "__";
+
+/// The prefix of variables overriding file keys by path, if the struct has a prefix.
+pub(crate) fn env_path_prefix(struct_attributes: &ConfigAttributes) -> Option<String> {
+    let prefix = struct_attributes.prefix.as_ref()?;
+    Some(format!(
+        "{}{}",
+        env_case_name(prefix, struct_attributes, &ConfigAttributes::default()),
+        PATH_SEPARATOR
+    ))
+}
+
+pub(crate) fn env_var_name(
+    field_name: &str,
+    struct_attributes: &ConfigAttributes,
+    attr: &ConfigAttributes,
+) -> String {
+    let mut env_var_name = env_field_name(field_name, struct_attributes, attr);
+
+    if let Some(key) = attr.key.as_ref().or(attr.env.first()) {
+        env_var_name = env_case_name(key, struct_attributes, attr);
+    } else if attr.prefix.is_some() || struct_attributes.prefix.is_some() {
+        let prefix = attr
+            .prefix
+            .as_ref()
+            .unwrap_or(
+                struct_attributes
+                    .prefix
+                    .as_ref()
+                    .unwrap_or(&String::from(DEFAULT_PREFIX)),
+            )
+            .clone();
+
+        env_var_name = format!(
+            "{}{}{}",
+            env_case_name(&prefix, struct_attributes, attr),
+            separator(struct_attributes, attr),
+            env_var_name
+        );
+    }
+
+    env_var_name
+}
+
 pub fn generate_from_env(
     name: &Ident,
+    generics: &Generics,
     struct_attributes: &ConfigAttributes,
-    field_data: &[(&Ident, &Type, ConfigAttributes)],
+    field_data: &[(Member, &Type, ConfigAttributes)],
 ) -> TokenStream {
     let layer_name = format_ident!("{}{}", name, SUFFIX);
+    // Fields of generic types are only parsed from environment variables if they implement `FromStr`.
+    let parsed_types = field_data
+        .iter()
+        .filter(|(_, ty, attr)| {
+            !(attr.skip || struct_attributes.skip || attr.nest || is_phantom(ty))
+                && uses_type_param(ty.to_token_stream(), generics)
+        })
+        .map(|(_, ty, _)| parse_quote! { #ty: ::core::str::FromStr });
+    let generics = add_predicates(generics, parsed_types);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let env_items = field_data.iter().map(|(field_name, ty, attr)| {
-        let mut env_var_name = field_name.to_string().to_uppercase();
-
-        if let Some(ref key) = attr.key {
-            env_var_name = key.clone().to_uppercase();
-        } else if attr.prefix.is_some() || struct_attributes.prefix.is_some() {
-            let separator = attr
-                .separator
-                .as_ref()
-                .unwrap_or(
-                    struct_attributes
-                        .separator
-                        .as_ref()
-                        .unwrap_or(&String::from(DEFAULT_SEPARATOR)),
-                )
-                .clone();
-
-            let prefix = attr
-                .prefix
-                .as_ref()
-                .unwrap_or(
-                    struct_attributes
-                        .prefix
-                        .as_ref()
-                        .unwrap_or(&String::from(DEFAULT_PREFIX)),
-                )
-                .to_uppercase();
-
-            env_var_name = format!(
-                "{}{}{}",
-                prefix,
-                separator,
-                field_name.to_string().to_uppercase()
-            );
-        }
-
-        if attr.skip || struct_attributes.skip {
-            quote! { #field_name: None }
-        } else if attr.nest {
-            let ty_ident = get_ident_from_type(ty);
-            let nested_builder = format_ident!("{}{}", ty_ident, SUFFIX);
-            quote! { #field_name: #nested_builder::from_env() }
-        } else {
-            quote! {
-                #field_name: match ::std::env::var(#env_var_name) {
-                    Ok(val) => val.parse::<#ty>().ok(),
-                    Err(_) => None
-                }
-            }
-        }
-    });
-
-    let env_keys = field_data.iter().map(|(field_name, _, attr)| {
-        let mut env_var_name = field_name.to_string().to_uppercase();
-
-        if let Some(ref key) = attr.key {
-            env_var_name = key.clone().to_uppercase();
-        } else if attr.prefix.is_some() || struct_attributes.prefix.is_some() {
-            let separator = attr
-                .separator
-                .as_ref()
-                .unwrap_or(
-                    struct_attributes
-                        .separator
-                        .as_ref()
-                        .unwrap_or(&String::from(DEFAULT_SEPARATOR)),
-                )
-                .clone();
-
-            let prefix = attr
-                .prefix
-                .as_ref()
-                .unwrap_or(
-                    struct_attributes
-                        .prefix
-                        .as_ref()
-                        .unwrap_or(&String::from(DEFAULT_PREFIX)),
-                )
-                .to_uppercase();
-
-            env_var_name = format!(
-                "{}{}{}",
-                prefix,
-                separator,
-                field_name.to_string().to_uppercase()
-            );
-        }
-        quote! { stringify!(#field_name).to_string(), #env_var_name.to_string() }
-    });
+    let EnvFields {
+        items: env_items,
+        keys: env_keys,
+        vars: mut env_vars,
+        prefixes: env_prefixes,
+        schema: env_schema,
+    } = generate_env_fields(
+        struct_attributes,
+        &generics,
+        field_data,
+        quote! { defaults },
+    );
+    env_vars.extend(generate_env_json_var(struct_attributes));
+    let env_case_insensitive_def = generate_env_case_insensitive(struct_attributes);
 
     quote! {
         #[automatically_derived]
-        impl ::confgr::core::FromEnv for #layer_name {
-            fn from_env() -> Self {
+        impl #impl_generics ::confgr::core::FromEnv for #layer_name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn from_env_prefixed<F>(prefix: Option<&str>, lookup: F) -> Self
+            where
+                F: Fn(&str) -> Option<String>,
+            {
                 Self {
                     #( #env_items ),*
                 }
             }
 
-             fn get_env_keys() -> ::std::collections::HashMap<String, String> {
+             #[allow(unused_variables)]
+             fn get_env_keys_prefixed(prefix: Option<&str>) -> ::std::collections::HashMap<String, String> {
                  let mut map = ::std::collections::HashMap::new();
-                 #(
-                     map.insert(#env_keys);
-                 )*
+                 #( #env_keys )*
                  map
              }
+
+            #[allow(unused_variables)]
+            fn get_env_vars_prefixed(prefix: Option<&str>) -> Vec<String> {
+                let mut vars = Vec::new();
+                #( #env_vars )*
+                vars
+            }
+
+            #[allow(unused_variables)]
+            fn get_env_prefixes_prefixed(prefix: Option<&str>) -> Vec<String> {
+                let mut prefixes = Vec::new();
+                #( #env_prefixes )*
+                prefixes
+            }
+
+            #[allow(unused_variables)]
+            fn env_schema_prefixed(prefix: Option<&str>, defaults: &Self) -> Vec<::confgr::core::EnvKey> {
+                let mut schema = Vec::new();
+                #( #env_schema )*
+                schema
+            }
+
+            #env_case_insensitive_def
+        }
+    }
+}
+
+/// Generates the statement listing the `env_json` variable among the variables read.
+pub(crate) fn generate_env_json_var(struct_attributes: &ConfigAttributes) -> Option<TokenStream> {
+    let env_json = struct_attributes.env_json.as_ref()?;
+    Some(quote! { vars.push(#env_json.to_string()); })
+}
+
+/// Overrides `env_case_insensitive` when the attribute is set.
+pub(crate) fn generate_env_case_insensitive(struct_attributes: &ConfigAttributes) -> TokenStream {
+    if struct_attributes.env_case_insensitive {
+        quote! {
+            fn env_case_insensitive() -> bool { true }
         }
+    } else {
+        quote! {}
     }
 }
+
+/// Generates an expression evaluating to the environment variable of a field. Unless the field sets
+/// its own `key` or `prefix`, the variable is named after the runtime `prefix` when one is given.
+pub(crate) fn env_var_expr(
+    field_name: &str,
+    struct_attributes: &ConfigAttributes,
+    attr: &ConfigAttributes,
+) -> TokenStream {
+    let env_var_name = env_var_name(field_name, struct_attributes, attr);
+
+    if attr.key.is_some() || !attr.env.is_empty() || attr.prefix.is_some() {
+        quote! { #env_var_name.to_string() }
+    } else {
+        let field_name = env_field_name(field_name, struct_attributes, attr);
+        quote! {
+            prefix.map_or_else(|| #env_var_name.to_string(), |prefix| format!("{}{}", prefix, #field_name))
+        }
+    }
+}
+
+/// The generated pieces of the `FromEnv` methods for a list of fields.
+pub(crate) struct EnvFields {
+    /// Field initializers of `from_env_prefixed`, reading from `lookup`.
+    pub items: Vec<TokenStream>,
+    /// Statements inserting each `(field, variable)` pair into the `map` of `get_env_keys_prefixed`.
+    pub keys: Vec<TokenStream>,
+    /// Statements pushing every variable read, aliases included, into the `vars` of
+    /// `get_env_vars_prefixed`.
+    pub vars: Vec<TokenStream>,
+    /// Statements pushing the variable prefixes into the `prefixes` of `get_env_prefixes_prefixed`.
+    pub prefixes: Vec<TokenStream>,
+    /// Statements pushing the `EnvKey` of each field into the `schema` of `env_schema_prefixed`,
+    /// with default values read from the fields of the `defaults` layer.
+    pub schema: Vec<TokenStream>,
+}
+
+pub(crate) fn generate_env_fields(
+    struct_attributes: &ConfigAttributes,
+    generics: &Generics,
+    field_data: &[(Member, &Type, ConfigAttributes)],
+    defaults: TokenStream,
+) -> EnvFields {
+    // The single field of a newtype struct is read from the struct's prefix itself.
+    let newtype_key = match field_data {
+        [(Member::Unnamed(_), _, attr)] if attr.key.is_none() && attr.env.is_empty() => {
+            struct_attributes.prefix.clone()
+        }
+        _ => None,
+    };
+
+    // Flattened fields are named as if they were fields of this struct.
+    let flatten_prefix = match &struct_attributes.prefix {
+        Some(prefix) => format!(
+            "{}{}",
+            env_case_name(prefix, struct_attributes, &ConfigAttributes::default()),
+            separator(struct_attributes, &ConfigAttributes::default())
+        ),
+        None => String::new(),
+    };
+
+    let env_var_names: Vec<TokenStream> = field_data
+        .iter()
+        .map(|(field_name, _, attr)| match &newtype_key {
+            Some(key) => {
+                let key = env_case_name(key, struct_attributes, attr);
+                quote! { #key.to_string() }
+            }
+            None => env_var_expr(&member_name(field_name), struct_attributes, attr),
+        })
+        .collect();
+
+    // Further candidates of `env`, tried in order when the first one is not set.
+    let env_fallbacks: Vec<Vec<String>> = field_data
+        .iter()
+        .map(|(_, _, attr)| {
+            attr.env
+                .iter()
+                .skip(1)
+                .map(|key| env_case_name(key, struct_attributes, attr))
+                .collect()
+        })
+        .collect();
+
+    let env_items = field_data
+        .iter()
+        .zip(&env_var_names)
+        .zip(&env_fallbacks)
+        .map(|(((field_name, ty, attr), env_var_name), env_fallbacks)| {
+            let lookup_value = quote! {
+                lookup(&env_var_name) #( .or_else(|| lookup(#env_fallbacks)) )*
+            };
+            if attr.skip || struct_attributes.skip || is_phantom(ty) {
+                quote! { #field_name: None }
+            } else if attr.flatten {
+                let nested_builder = nested_layer(ty);
+                quote! {
+                    #field_name: <#nested_builder as ::confgr::core::FromEnv>::from_env_prefixed(
+                        Some(prefix.unwrap_or(#flatten_prefix)),
+                        &lookup,
+                    )
+                }
+            } else if attr.nest {
+                let nested_builder = nested_layer(ty);
+                quote! {
+                    #field_name: <#nested_builder as ::confgr::core::FromEnv>::from_env_with(&lookup)
+                }
+            } else if !attr.env_alias.is_empty() || attr.deprecated.is_some() {
+                let env_aliases = env_aliases(struct_attributes, attr);
+                let alias_fallback = if env_aliases.is_empty() {
+                    quote! {}
+                } else {
+                    quote! {
+                        .or_else(|| {
+                            [#( #env_aliases ),*].into_iter().find_map(|alias| {
+                                let value = lookup(alias)?;
+                                ::confgr::core::warn_alias(&env_var_name, alias, ::confgr::core::Source::Env);
+                                Some(value)
+                            })
+                        })
+                    }
+                };
+                let deprecation = if let Some(message) = &attr.deprecated {
+                    let since = match &attr.since {
+                        Some(since) => quote! { Some(#since) },
+                        None => quote! { None },
+                    };
+                    quote! {
+                        if value.is_some() {
+                            ::confgr::core::warn_deprecated(&env_var_name, #message, #since, ::confgr::core::Source::Env);
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #field_name: {
+                        let env_var_name = #env_var_name;
+                        let value = #lookup_value #alias_fallback;
+                        #deprecation
+                        value.and_then(|val| val.parse::<#ty>().ok())
+                    }
+                }
+            } else {
+                quote! {
+                    #field_name: {
+                        let env_var_name = #env_var_name;
+                        #lookup_value.and_then(|val| val.parse::<#ty>().ok())
+                    }
+                }
+            }
+        })
+        .collect();
+
+    let env_keys = field_data
+        .iter()
+        .zip(&env_var_names)
+        .zip(&env_fallbacks)
+        .map(|(((field_name, ty, attr), env_var_name), env_fallbacks)| {
+            if attr.flatten {
+                let nested_builder = nested_layer(ty);
+                quote! {
+                    map.extend(<#nested_builder as ::confgr::core::FromEnv>::get_env_keys_prefixed(
+                        Some(prefix.unwrap_or(#flatten_prefix)),
+                    ));
+                }
+            } else if env_fallbacks.is_empty() {
+                let field_name = member_name(field_name);
+                quote! { map.insert(#field_name.to_string(), #env_var_name); }
+            } else {
+                let field_name = member_name(field_name);
+                quote! {
+                    map.insert(#field_name.to_string(), [#env_var_name #(, #env_fallbacks.to_string())*].join(", "));
+                }
+            }
+        })
+        .collect();
+
+    let env_vars = field_data
+        .iter()
+        .zip(&env_var_names)
+        .zip(&env_fallbacks)
+        .map(|(((_, ty, attr), env_var_name), env_fallbacks)| {
+            let nested_builder = nested_layer(ty);
+            if attr.flatten {
+                quote! {
+                    vars.extend(<#nested_builder as ::confgr::core::FromEnv>::get_env_vars_prefixed(
+                        Some(prefix.unwrap_or(#flatten_prefix)),
+                    ));
+                }
+            } else if attr.nest {
+                quote! {
+                    vars.extend(<#nested_builder as ::confgr::core::FromEnv>::get_env_vars());
+                }
+            } else {
+                let env_aliases = env_aliases(struct_attributes, attr);
+                quote! {
+                    vars.push(#env_var_name);
+                    #( vars.push(#env_fallbacks.to_string()); )*
+                    #( vars.push(#env_aliases.to_string()); )*
+                }
+            }
+        })
+        .collect();
+
+    let mut env_prefixes = vec![quote! {
+        prefixes.push(prefix.unwrap_or(#flatten_prefix).to_string());
+    }];
+    env_prefixes.extend(field_data.iter().filter_map(|(_, ty, attr)| {
+        let nested_builder = nested_layer(ty);
+        if attr.flatten {
+            Some(quote! {
+                prefixes.extend(<#nested_builder as ::confgr::core::FromEnv>::get_env_prefixes_prefixed(
+                    Some(prefix.unwrap_or(#flatten_prefix)),
+                ));
+            })
+        } else if attr.nest {
+            Some(quote! {
+                prefixes.extend(<#nested_builder as ::confgr::core::FromEnv>::get_env_prefixes());
+            })
+        } else if attr.key.is_none() && attr.env.is_empty() {
+            let field_prefix = format!(
+                "{}{}",
+                env_case_name(attr.prefix.as_ref()?, struct_attributes, attr),
+                separator(struct_attributes, attr)
+            );
+            Some(quote! { prefixes.push(#field_prefix.to_string()); })
+        } else {
+            None
+        }
+    }));
+
+    let env_schema = field_data
+        .iter()
+        .zip(&env_var_names)
+        .zip(&env_fallbacks)
+        .map(|(((field_name, ty, attr), env_var_name), env_fallbacks)| {
+            let nested_builder = nested_layer(ty);
+            let name = member_name(field_name);
+            let type_name = type_name(ty);
+            let skipped = attr.skip || struct_attributes.skip || is_phantom(ty);
+            let doc = match &attr.doc {
+                Some(doc) => quote! { Some(#doc.to_string()) },
+                None => quote! { None },
+            };
+            if attr.flatten {
+                quote! {
+                    schema.extend(<#nested_builder as ::confgr::core::FromEnv>::env_schema_prefixed(
+                        Some(prefix.unwrap_or(#flatten_prefix)),
+                        &#defaults.#field_name,
+                    ));
+                }
+            } else if attr.nest {
+                quote! {
+                    schema.push(::confgr::core::EnvKey {
+                        field_path: #name.to_string(),
+                        var_names: Vec::new(),
+                        type_name: #type_name,
+                        skipped: #skipped,
+                        default_repr: None,
+                        doc: #doc,
+                        children: <#nested_builder as ::confgr::core::FromEnv>::env_schema_prefixed(None, &#defaults.#field_name)
+                            .into_iter()
+                            .map(|key| key.with_parent(#name))
+                            .collect(),
+                    });
+                }
+            } else {
+                let var_names = if skipped {
+                    quote! { Vec::new() }
+                } else {
+                    quote! { vec![#env_var_name #(, #env_fallbacks.to_string())*] }
+                };
+                // Type parameters are not required to implement `Debug`.
+                let default_repr = if uses_type_param(ty.to_token_stream(), generics) {
+                    quote! { None }
+                } else {
+                    quote! { #defaults.#field_name.as_ref().map(|value| format!("{:?}", value)) }
+                };
+                quote! {
+                    schema.push(::confgr::core::EnvKey {
+                        field_path: #name.to_string(),
+                        var_names: #var_names,
+                        type_name: #type_name,
+                        skipped: #skipped,
+                        default_repr: #default_repr,
+                        doc: #doc,
+                        children: Vec::new(),
+                    });
+                }
+            }
+        })
+        .collect();
+
+    EnvFields {
+        items: env_items,
+        keys: env_keys,
+        vars: env_vars,
+        prefixes: env_prefixes,
+        schema: env_schema,
+    }
+}
+
+fn env_aliases(struct_attributes: &ConfigAttributes, attr: &ConfigAttributes) -> Vec<String> {
+    attr.env_alias
+        .iter()
+        .map(|alias| env_case_name(alias, struct_attributes, attr))
+        .collect()
+}
+
+/// The type of a field as written, without the spacing between tokens added by `to_string`.
+fn type_name(ty: &Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let chars: Vec<char> = tokens.chars().collect();
+    let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+
+    chars
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| {
+            **c != ' ' || (is_word(chars.get(i.wrapping_sub(1))) && is_word(chars.get(i + 1)))
+        })
+        .map(|(_, c)| *c)
+        .collect::<String>()
+        .replace(',', ", ")
+}
+
+/// Whether the field is a [`PhantomData`](std::marker::PhantomData) marker, which is never read
+/// from environment variables.
+fn is_phantom(ty: &Type) -> bool {
+    matches!(ty, Type::Path(type_path) if type_path.path.segments.last().is_some_and(|segment| segment.ident == "PhantomData"))
+}
+
+/// Whether the tokens of a field type mention any of the type parameters of `generics`.
+fn uses_type_param(tokens: TokenStream, generics: &Generics) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => generics.type_params().any(|param| param.ident == ident),
+        TokenTree::Group(group) => uses_type_param(group.stream(), generics),
+        _ => false,
+    })
+}
@@ -1,10 +1,53 @@
-use crate::{ConfigAttributes, SUFFIX};
+use crate::{add_predicates, env, member_name, nested_layer, ConfigAttributes, SUFFIX};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::Ident;
+use syn::{parse_quote, Generics, Ident, Member, Type};
 
-pub(crate) fn generate_from_file(name: &Ident, attributes: &ConfigAttributes) -> TokenStream {
+/// Supported values of the `rename_all` attribute, as accepted by serde.
+pub(crate) const RENAME_RULES: [&str; 8] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Generates an expression evaluating to the file key of a field, renamed at runtime with the
+/// struct's `rename_all` unless the field sets its own `name`.
+fn file_key(
+    field_name: &Member,
+    attributes: &ConfigAttributes,
+    attr: &ConfigAttributes,
+) -> TokenStream {
+    match (&attr.name, &attributes.rename_all) {
+        (Some(name), _) => quote! { #name.to_string() },
+        (None, Some(rule)) => {
+            let key = member_name(field_name);
+            quote! { ::confgr::core::rename_key(#key, #rule) }
+        }
+        (None, None) => {
+            let key = member_name(field_name);
+            quote! { #key.to_string() }
+        }
+    }
+}
+
+pub(crate) fn generate_from_file(
+    name: &Ident,
+    generics: &Generics,
+    attributes: &ConfigAttributes,
+    field_data: &[(Member, &Type, ConfigAttributes)],
+    tag_key: Option<&str>,
+) -> TokenStream {
     let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let generics = add_predicates(
+        generics,
+        [parse_quote! { Self: ::serde::de::DeserializeOwned + ::confgr::core::Merge }],
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     if attributes.path.is_some() && attributes.default_path.is_some() {
         panic!("'path' and 'default_path' attributes cannot be used alongside eachother");
@@ -14,50 +57,53 @@ pub(crate) fn generate_from_file(name: &Ident, attributes: &ConfigAttributes) ->
         match (&attributes.path, &attributes.default_path) {
             (Some(path), None) => {
                 quote! {
-                    fn get_file_path() -> Option<String> {
-                        match std::env::var(#env_path) {
-                            Ok(env_val) => if std::path::Path::new(&env_val).exists() { Some(env_val) }
-                                           else if std::path::Path::new(#path).exists() { Some(#path.to_string()) }
-                                           else { panic!("'env_path' and 'path' attributes resolve to non-existent or invalid files.") },
-                            Err(_) => if std::path::Path::new(#path).exists() { Some(#path.to_string()) }
-                                      else { panic!("'env_path' variable is not set and the provided 'path' attribute is invalid or references a non-existent file.") }
+                    fn get_file_path_with<F>(lookup: F) -> Option<String>
+                    where
+                        F: Fn(&str) -> Option<String>,
+                    {
+                        match lookup(#env_path) {
+                            Some(env_val) => if std::path::Path::new(&env_val).exists() { Some(env_val) }
+                                             else if std::path::Path::new(#path).exists() { Some(#path.to_string()) }
+                                             else { panic!("'env_path' and 'path' attributes resolve to non-existent or invalid files.") },
+                            None => if std::path::Path::new(#path).exists() { Some(#path.to_string()) }
+                                    else { panic!("'env_path' variable is not set and the provided 'path' attribute is invalid or references a non-existent file.") }
                         }
                     }
                 }
             }
             (None, Some(default_path)) => {
                 quote! {
-                    fn get_file_path() -> Option<String> {
-                        match std::env::var(#env_path) {
-                            Ok(env_val) => if std::path::Path::new(&env_val).exists() { Some(env_val) }
-                                           else if std::path::Path::new(#default_path).exists() { Some(#default_path.to_string()) }
-                                           else { None },
-                            Err(_) => if std::path::Path::new(#default_path).exists() { Some(#default_path.to_string()) }
-                                      else { None }
+                    fn get_file_path_with<F>(lookup: F) -> Option<String>
+                    where
+                        F: Fn(&str) -> Option<String>,
+                    {
+                        match lookup(#env_path) {
+                            Some(env_val) => if std::path::Path::new(&env_val).exists() { Some(env_val) }
+                                             else if std::path::Path::new(#default_path).exists() { Some(#default_path.to_string()) }
+                                             else { None },
+                            None => if std::path::Path::new(#default_path).exists() { Some(#default_path.to_string()) }
+                                    else { None }
                         }
                     }
                 }
             }
             _ => {
                 quote! {
-                    fn get_file_path() -> Option<String> {
-                        std::env::var(#env_path)
-                            .map(|env_val| {
-                                if std::path::Path::new(&env_val).exists() {
-                                    Some(env_val)
-                                } else {
-                                    None
-                                }
-                        })
-                        .ok()
-                        .flatten()
+                    fn get_file_path_with<F>(lookup: F) -> Option<String>
+                    where
+                        F: Fn(&str) -> Option<String>,
+                    {
+                        lookup(#env_path).filter(|env_val| std::path::Path::new(env_val).exists())
                     }
                 }
             }
         }
     } else if let Some(path) = &attributes.path {
         quote! {
-            fn get_file_path() -> Option<String> {
+            fn get_file_path_with<F>(_lookup: F) -> Option<String>
+            where
+                F: Fn(&str) -> Option<String>,
+            {
                 if std::path::Path::new(#path).exists() {
                     Some(#path.to_string())
                 } else {
@@ -67,7 +113,10 @@ pub(crate) fn generate_from_file(name: &Ident, attributes: &ConfigAttributes) ->
         }
     } else if let Some(default_path) = &attributes.default_path {
         quote! {
-            fn get_file_path() -> Option<String> {
+            fn get_file_path_with<F>(_lookup: F) -> Option<String>
+            where
+                F: Fn(&str) -> Option<String>,
+            {
                 if std::path::Path::new(#default_path).exists() {
                     Some(#default_path.to_string())
                 } else {
@@ -77,42 +126,288 @@ pub(crate) fn generate_from_file(name: &Ident, attributes: &ConfigAttributes) ->
         }
     } else {
         quote! {
-            fn get_file_path() -> Option<String> { None }
+            fn get_file_path_with<F>(_lookup: F) -> Option<String>
+            where
+                F: Fn(&str) -> Option<String>,
+            {
+                None
+            }
         }
     };
 
+    let get_embedded_file_def = if let Some(embed) = &attributes.embed {
+        let format = match std::path::Path::new(embed)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("toml") => quote! { ::confgr::core::Format::Toml },
+            Some("json") => quote! { ::confgr::core::Format::Json },
+            Some("yaml") | Some("yml") => quote! { ::confgr::core::Format::Yaml },
+            Some("ini") => quote! { ::confgr::core::Format::Ini },
+            Some("ron") => quote! { ::confgr::core::Format::Ron },
+            Some("json5") => quote! { ::confgr::core::Format::Json5 },
+            _ => panic!(
+                "The 'embed' attribute value '{}' does not have a supported file extension.",
+                embed
+            ),
+        };
+
+        let contents = if std::path::Path::new(embed).is_absolute() {
+            quote! { include_str!(#embed) }
+        } else {
+            let relative_path = format!("/{}", embed);
+            quote! { include_str!(concat!(env!("CARGO_MANIFEST_DIR"), #relative_path)) }
+        };
+
+        quote! {
+            fn get_embedded_file() -> Option<(&'static str, ::confgr::core::Format)> {
+                Some((#contents, #format))
+            }
+        }
+    } else {
+        quote! {
+            fn get_embedded_file() -> Option<(&'static str, ::confgr::core::Format)> { None }
+        }
+    };
+
+    let discovered_paths = if let Some(discover) = &attributes.discover {
+        quote! { ::confgr::core::discover_files_with(#discover, &lookup) }
+    } else {
+        quote! { ::std::vec::Vec::new() }
+    };
+
+    let dir_paths = if let Some(dir) = &attributes.dir {
+        quote! { file_paths.extend(::confgr::core::dir_files(#dir)); }
+    } else {
+        quote! {}
+    };
+
+    let interpolates_def = if attributes.interpolate {
+        quote! {
+            fn interpolates() -> bool { true }
+        }
+    } else {
+        quote! {}
+    };
+
+    let file_aliases = field_data.iter().map(|(field_name, ty, attr)| {
+        let key = file_key(field_name, attributes, attr);
+        let aliases = &attr.alias;
+        let nested_aliases = if attr.flatten {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                aliases.extend(<#nested_builder as ::confgr::core::FromFile>::get_file_aliases());
+            }
+        } else if attr.nest {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                for (key, alias) in <#nested_builder as ::confgr::core::FromFile>::get_file_aliases() {
+                    for parent in [#key #(, #aliases.to_string())*] {
+                        aliases.push((format!("{}.{}", #key, key), format!("{}.{}", parent, alias)));
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let own_aliases = if aliases.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                for alias in [#( #aliases ),*] {
+                    aliases.push((#key, alias.to_string()));
+                }
+            }
+        };
+        quote! {
+            #own_aliases
+            #nested_aliases
+        }
+    });
+
+    let rename_all_def = if let Some(rule) = &attributes.rename_all {
+        quote! {
+            fn rename_all() -> Option<&'static str> { Some(#rule) }
+        }
+    } else {
+        quote! {}
+    };
+
+    let env_json_def = if let Some(env_json) = &attributes.env_json {
+        quote! {
+            fn get_env_json_key() -> Option<&'static str> { Some(#env_json) }
+        }
+    } else {
+        quote! {}
+    };
+
+    let env_path_prefix_def = if let Some(prefix) = env::env_path_prefix(attributes) {
+        quote! {
+            fn get_env_path_prefix() -> Option<&'static str> { Some(#prefix) }
+        }
+    } else {
+        quote! {}
+    };
+
+    let includes_def = if attributes.includes {
+        quote! {
+            fn includes() -> bool { true }
+        }
+    } else {
+        quote! {}
+    };
+
+    let strict_def = if attributes.strict {
+        quote! {
+            fn strict() -> bool { true }
+        }
+    } else {
+        quote! {}
+    };
+
+    let extra_keys = match tag_key {
+        Some(tag_key) => quote! { vec![#tag_key.to_string()] },
+        None => quote! { Vec::new() },
+    };
+
+    let file_keys = field_data.iter().map(|(field_name, ty, attr)| {
+        let key = file_key(field_name, attributes, attr);
+        let aliases = &attr.alias;
+        if attr.flatten {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                keys.extend(<#nested_builder as ::confgr::core::FromFile>::get_file_keys());
+            }
+        } else if attr.nest {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                for key in <#nested_builder as ::confgr::core::FromFile>::get_file_keys() {
+                    for parent in [#key #(, #aliases.to_string())*] {
+                        keys.push(format!("{}.{}", parent, key));
+                    }
+                }
+            }
+        } else {
+            quote! {
+                keys.push(#key);
+                #( keys.push(#aliases.to_string()); )*
+            }
+        }
+    });
+
+    let file_deprecations = field_data.iter().map(|(field_name, ty, attr)| {
+        let key = file_key(field_name, attributes, attr);
+        if let Some(message) = &attr.deprecated {
+            let since = match &attr.since {
+                Some(since) => quote! { Some(#since.to_string()) },
+                None => quote! { None },
+            };
+            quote! { deprecations.push((#key, #message.to_string(), #since)); }
+        } else if attr.flatten {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                deprecations.extend(<#nested_builder as ::confgr::core::FromFile>::get_file_deprecations());
+            }
+        } else if attr.nest {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                deprecations.extend(
+                    <#nested_builder as ::confgr::core::FromFile>::get_file_deprecations()
+                        .into_iter()
+                        .map(|(key, message, since)| (format!("{}.{}", #key, key), message, since)),
+                );
+            }
+        } else {
+            quote! {}
+        }
+    });
+
+    let flattened = field_data.iter().map(|(field_name, ty, attr)| {
+        let key = file_key(field_name, attributes, attr);
+        let aliases = &attr.alias;
+        if attr.flatten {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                let mut keys: Vec<String> = <#nested_builder as ::confgr::core::FromFile>::get_file_keys()
+                    .iter()
+                    .map(|key| key.split('.').next().unwrap_or(key).to_string())
+                    .collect();
+                keys.sort();
+                keys.dedup();
+                flattened.push((#key, keys));
+                for (path, keys) in <#nested_builder as ::confgr::core::FromFile>::get_flattened() {
+                    flattened.push((format!("{}.{}", #key, path), keys));
+                }
+            }
+        } else if attr.nest {
+            let nested_builder = nested_layer(ty);
+            quote! {
+                for (path, keys) in <#nested_builder as ::confgr::core::FromFile>::get_flattened() {
+                    for parent in [#key #(, #aliases.to_string())*] {
+                        flattened.push((format!("{}.{}", parent, path), keys.clone()));
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        }
+    });
+
     quote! {
         #[automatically_derived]
-        impl ::confgr::core::FromFile for #layer_name {
+        impl #impl_generics ::confgr::core::FromFile for #layer_name #ty_generics #where_clause {
+            #interpolates_def
+
             #get_file_path_def
 
-            fn from_file() -> Result<Self, ::confgr::core::ConfgrError> {
-                let file_path = Self::get_file_path().ok_or(::confgr::core::ConfgrError::NoFilePath)?;
+            fn get_file_paths_with<F>(lookup: F) -> Vec<String>
+            where
+                F: Fn(&str) -> Option<String>,
+            {
+                let mut file_paths: Vec<String> = #discovered_paths;
+                file_paths.extend(Self::get_file_path_with(&lookup));
+                #dir_paths
+                file_paths
+            }
 
-                Self::check_file()?;
+            #get_embedded_file_def
 
-                let config = ::confgr::config::Config::builder()
-                    .add_source(::confgr::config::File::with_name(&file_path))
-                    .build()?;
+            #strict_def
 
-                config.try_deserialize::<Self>().map_err(|e| ::confgr::core::ConfgrError::Config(e))
-            }
+            #includes_def
 
-            fn check_file() -> Result<(), ::confgr::core::ConfgrError> {
-                use ::std::fs::File;
-                use ::std::io::Read;
+            #rename_all_def
 
-                let file_path = Self::get_file_path().ok_or(::confgr::core::ConfgrError::NoFilePath)?;
+            #env_json_def
 
-                let mut file = File::open(&file_path)
-                    .map_err(|e| ::confgr::core::ConfgrError::File(e))?;
+            #env_path_prefix_def
 
-                let mut contents = String::new();
-                file.read_to_string(&mut contents)
-                    .map_err(|e| ::confgr::core::ConfgrError::File(e))?;
+            fn get_file_keys() -> Vec<String> {
+                #[allow(unused_mut)]
+                let mut keys: Vec<String> = #extra_keys;
+                #( #file_keys )*
+                keys
+            }
 
+            fn get_flattened() -> Vec<(String, Vec<String>)> {
+                #[allow(unused_mut)]
+                let mut flattened: Vec<(String, Vec<String>)> = Vec::new();
+                #( #flattened )*
+                flattened
+            }
+
+            fn get_file_aliases() -> Vec<(String, String)> {
+                #[allow(unused_mut)]
+                let mut aliases: Vec<(String, String)> = Vec::new();
+                #( #file_aliases )*
+                aliases
+            }
 
-                Ok(())
+            fn get_file_deprecations() -> Vec<(String, String, Option<String>)> {
+                #[allow(unused_mut)]
+                let mut deprecations: Vec<(String, String, Option<String>)> = Vec::new();
+                #( #file_deprecations )*
+                deprecations
             }
         }
     }
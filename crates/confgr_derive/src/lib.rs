@@ -1,17 +1,20 @@
 use quote::{quote, ToTokens};
 use syn::{
     parse_macro_input, punctuated::Punctuated, Attribute, Data, DeriveInput, Error, Expr, ExprLit,
-    Fields, Ident, Lit, Meta, Token, Type,
+    Fields, Generics, Index, Lit, Member, Meta, Token, Type, WherePredicate,
 };
 
 mod config;
 mod convert;
+mod dotenv;
+mod enums;
 mod env;
 mod file;
 mod merge;
 
 const SUFFIX: &str = "ConfgrLayer";
 const AUTOCONF_ATTRIBUTE: &str = "config";
+const DOC_ATTRIBUTE: &str = "doc";
 const PATH_ATTRIBUTE: &str = "path";
 const DEFAULT_PATH_ATTRIBUTE: &str = "default_path";
 const ENV_PATH_ATTRIBUTE: &str = "env_path";
@@ -21,6 +24,27 @@ const SEPARATOR_ATTRIBUTE: &str = "separator";
 const NEST_ATTRIBUTE: &str = "nest";
 const SKIP_ATTRIBUTE: &str = "skip";
 const NAME_ATTRIBUTE: &str = "name";
+const DOTENV_ATTRIBUTE: &str = "dotenv";
+const EMBED_ATTRIBUTE: &str = "embed";
+const DISCOVER_ATTRIBUTE: &str = "discover";
+const DIR_ATTRIBUTE: &str = "dir";
+const INTERPOLATE_ATTRIBUTE: &str = "interpolate";
+const MERGE_ATTRIBUTE: &str = "merge";
+const MERGE_WITH_ATTRIBUTE: &str = "merge_with";
+const TAG_ATTRIBUTE: &str = "tag";
+const FLATTEN_ATTRIBUTE: &str = "flatten";
+const ALIAS_ATTRIBUTE: &str = "alias";
+const ENV_ALIAS_ATTRIBUTE: &str = "env_alias";
+const DEPRECATED_ATTRIBUTE: &str = "deprecated";
+const SINCE_ATTRIBUTE: &str = "since";
+const STRICT_ATTRIBUTE: &str = "strict";
+const INCLUDES_ATTRIBUTE: &str = "includes";
+const ENV_CASE_ATTRIBUTE: &str = "env_case";
+const SEPARATE_WORDS_ATTRIBUTE: &str = "separate_words";
+const RENAME_ALL_ATTRIBUTE: &str = "rename_all";
+const ENV_ATTRIBUTE: &str = "env";
+const ENV_CASE_INSENSITIVE_ATTRIBUTE: &str = "env_case_insensitive";
+const ENV_JSON_ATTRIBUTE: &str = "env_json";
 
 #[proc_macro_derive(Config, attributes(config))]
 pub fn config_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -33,21 +57,59 @@ pub fn config_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 fn impl_config_derive(ast: &DeriveInput) -> Result<proc_macro2::TokenStream, Vec<syn::Error>> {
     let name = &ast.ident;
-    let struct_attributes =
-        parse_config_field_attributes(&ast.attrs).unwrap_or_else(|_| ConfigAttributes::default());
+    let generics = &ast.generics;
+    let struct_attributes = parse_config_field_attributes(&ast.attrs)?;
 
-    let field_data = extract_fields(ast)?;
+    // The fields of every variant of an enum share the enum's level in files.
+    let (layer_impl, from_impl, env_impl, file_fields) = match &ast.data {
+        Data::Struct(data) => {
+            let field_data = extract_fields(&data.fields)?;
+            (
+                merge::generate_layer(name, generics, &struct_attributes, &field_data),
+                convert::generate_conversion_impl(name, generics, &field_data),
+                env::generate_from_env(name, generics, &struct_attributes, &field_data),
+                field_data,
+            )
+        }
+        Data::Enum(_) if !generics.params.is_empty() => {
+            return Err(vec![syn::Error::new_spanned(
+                generics,
+                "Config derive macro does not support generic enums.",
+            )])
+        }
+        Data::Enum(data) => {
+            let (layer_impl, from_impl, env_impl) =
+                enums::generate_enum(name, &struct_attributes, data)?;
+            let field_data = data
+                .variants
+                .iter()
+                .filter_map(|variant| extract_fields(&variant.fields).ok())
+                .flatten()
+                .collect();
+            (layer_impl, from_impl, env_impl, field_data)
+        }
+        Data::Union(_) => {
+            return Err(vec![syn::Error::new_spanned(
+                ast,
+                "Config derive macro only supports structs and enums.",
+            )])
+        }
+    };
 
-    let layer_impl = merge::generate_layer(name, &struct_attributes, &field_data);
-    let config_impl = config::generate_config_impl(name);
-    let from_impl = convert::generate_conversion_impl(name, &field_data);
-    let env_impl = env::generate_from_env(name, &struct_attributes, &field_data);
-    let file_impl = file::generate_from_file(name, &struct_attributes);
+    let config_impl = config::generate_config_impl(name, generics);
+    let dotenv_impl = dotenv::generate_from_dotenv(name, generics, &struct_attributes);
+    let tag_key = match &ast.data {
+        Data::Enum(_) => Some(enums::tag_key(&struct_attributes)),
+        _ => None,
+    };
+    let file_impl =
+        file::generate_from_file(name, generics, &struct_attributes, &file_fields, tag_key);
 
     let expanded = quote! {
         #layer_impl
         #from_impl
         #env_impl
+        #dotenv_impl
         #file_impl
         #config_impl
     };
@@ -56,39 +118,117 @@ fn impl_config_derive(ast: &DeriveInput) -> Result<proc_macro2::TokenStream, Vec
 }
 
 pub(crate) fn extract_fields(
-    input: &DeriveInput,
-) -> Result<Vec<(&Ident, &Type, ConfigAttributes)>, Vec<syn::Error>> {
-    if let Data::Struct(data) = &input.data {
-        if let Fields::Named(fields) = &data.fields {
-            let mut field_data = Vec::new();
-            let mut errors = Vec::new();
+    fields: &Fields,
+) -> Result<Vec<(Member, &Type, ConfigAttributes)>, Vec<syn::Error>> {
+    let mut field_data = Vec::new();
+    let mut errors = Vec::new();
 
-            for f in fields.named.iter() {
-                match parse_config_field_attributes(&f.attrs) {
-                    Ok(attributes) => {
-                        field_data.push((f.ident.as_ref().unwrap(), &f.ty, attributes))
-                    }
-                    Err(mut errs) => errors.append(&mut errs),
-                }
+    for (index, f) in fields.iter().enumerate() {
+        match parse_config_field_attributes(&f.attrs) {
+            Ok(attributes) if attributes.struct_only().is_some() => {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    format!(
+                        "'{}' can only be used on structs, not on fields",
+                        attributes.struct_only().unwrap_or_default()
+                    ),
+                ))
             }
-
-            if errors.is_empty() {
-                Ok(field_data)
-            } else {
-                Err(errors)
+            Ok(attributes)
+                if attributes.nest
+                    && (attributes.merge.is_some() || attributes.merge_with.is_some()) =>
+            {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    "'merge' and 'merge_with' cannot be used on nested fields, which are merged by their own fields.",
+                ))
             }
-        } else {
-            Err(vec![syn::Error::new_spanned(
-                &data.fields,
-                "Config derive macro only supports structs with named fields.",
-            )])
+            Ok(attributes) if attributes.nest && attributes.deprecated.is_some() => {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    "'deprecated' cannot be used on nested fields, deprecate their own fields instead.",
+                ))
+            }
+            Ok(attributes) if attributes.since.is_some() && attributes.deprecated.is_none() => {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    "'since' can only be used alongside 'deprecated'",
+                ))
+            }
+            Ok(attributes) if attributes.nest && !attributes.env.is_empty() => {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    "'env' cannot be used on nested fields, which are read from their own fields.",
+                ))
+            }
+            Ok(attributes) if attributes.key.is_some() && !attributes.env.is_empty() => {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    "'key' and 'env' cannot be used alongside eachother",
+                ))
+            }
+            Ok(attributes) if attributes.merge.is_some() && attributes.merge_with.is_some() => {
+                errors.push(syn::Error::new_spanned(
+                    f,
+                    "'merge' and 'merge_with' cannot be used alongside eachother",
+                ))
+            }
+            Ok(attributes) => {
+                let member = match &f.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(Index::from(index)),
+                };
+                field_data.push((member, &f.ty, attributes))
+            }
+            Err(mut errs) => errors.append(&mut errs),
         }
+    }
+
+    if errors.is_empty() {
+        Ok(field_data)
     } else {
-        Err(vec![syn::Error::new_spanned(
-            input,
-            "Config derive macro only supports structs.",
-        )])
+        Err(errors)
+    }
+}
+
+/// Adds `predicates` to the where clause of `generics`, for impls with additional requirements.
+pub(crate) fn add_predicates(
+    generics: &Generics,
+    predicates: impl IntoIterator<Item = WherePredicate>,
+) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+/// The name of a field as used for environment variables, i.e. its identifier or its index.
+pub(crate) fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// Converts a `CamelCase` variant name into `snake_case`, e.g. `LocalDisk` into `local_disk`.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
     }
+
+    snake
 }
 
 pub(crate) fn parse_config_field_attributes(
@@ -97,6 +237,23 @@ pub(crate) fn parse_config_field_attributes(
     let mut attributes = ConfigAttributes::new();
     let mut errors = Vec::new();
 
+    let doc: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident(DOC_ATTRIBUTE))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(named_value) => match &named_value.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    if !doc.is_empty() {
+        attributes.doc = Some(doc.join("\n"));
+    }
+
     for attr in attrs
         .iter()
         .filter(|a| a.path().is_ident(AUTOCONF_ATTRIBUTE))
@@ -108,6 +265,25 @@ pub(crate) fn parse_config_field_attributes(
                     match meta {
                         Meta::Path(path) if path.is_ident(SKIP_ATTRIBUTE) => attributes.skip = true,
                         Meta::Path(path) if path.is_ident(NEST_ATTRIBUTE) => attributes.nest = true,
+                        Meta::Path(path) if path.is_ident(FLATTEN_ATTRIBUTE) => {
+                            attributes.nest = true;
+                            attributes.flatten = true
+                        }
+                        Meta::Path(path) if path.is_ident(INTERPOLATE_ATTRIBUTE) => {
+                            attributes.interpolate = true
+                        }
+                        Meta::Path(path) if path.is_ident(STRICT_ATTRIBUTE) => {
+                            attributes.strict = true
+                        }
+                        Meta::Path(path) if path.is_ident(INCLUDES_ATTRIBUTE) => {
+                            attributes.includes = true
+                        }
+                        Meta::Path(path) if path.is_ident(SEPARATE_WORDS_ATTRIBUTE) => {
+                            attributes.separate_words = true
+                        }
+                        Meta::Path(path) if path.is_ident(ENV_CASE_INSENSITIVE_ATTRIBUTE) => {
+                            attributes.env_case_insensitive = true
+                        }
                         Meta::NameValue(named_value)
                             if named_value.path.is_ident(PATH_ATTRIBUTE) =>
                         {
@@ -187,6 +363,22 @@ pub(crate) fn parse_config_field_attributes(
                                 ));
                             }
                         }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(ENV_JSON_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(env_json),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.env_json = Some(env_json.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string for 'env_json'",
+                                ));
+                            }
+                        }
                         Meta::NameValue(named_value)
                             if named_value.path.is_ident(DEFAULT_PATH_ATTRIBUTE) =>
                         {
@@ -219,6 +411,246 @@ pub(crate) fn parse_config_field_attributes(
                                 ));
                             }
                         }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(DOTENV_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(dotenv),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.dotenv = Some(dotenv.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string for 'dotenv'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(EMBED_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(embed),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.embed = Some(embed.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a valid path for 'embed'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(DISCOVER_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(discover),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.discover = Some(discover.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string for 'discover'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(DIR_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(dir), ..
+                            }) = &named_value.value
+                            {
+                                attributes.dir = Some(dir.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a valid path for 'dir'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(MERGE_ATTRIBUTE) =>
+                        {
+                            match &named_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Str(merge),
+                                    ..
+                                }) if merge::MERGE_STRATEGIES.contains(&merge.value().as_str()) => {
+                                    attributes.merge = Some(merge.value());
+                                }
+                                _ => errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected one of \"replace\", \"append\" or \"union\" for 'merge'",
+                                )),
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(MERGE_WITH_ATTRIBUTE) =>
+                        {
+                            match &named_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Str(merge_with),
+                                    ..
+                                }) if merge_with.parse::<syn::Path>().is_ok() => {
+                                    attributes.merge_with = Some(merge_with.value());
+                                }
+                                _ => errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a path to a function for 'merge_with'",
+                                )),
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(ALIAS_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(alias),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.alias.push(alias.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string for 'alias'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(ENV_ALIAS_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(env_alias),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.env_alias.push(env_alias.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string for 'env_alias'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(ENV_CASE_ATTRIBUTE) =>
+                        {
+                            match &named_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Str(env_case),
+                                    ..
+                                }) if env::ENV_CASES.contains(&env_case.value().as_str()) => {
+                                    attributes.env_case = Some(env_case.value());
+                                }
+                                _ => errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected one of \"SCREAMING_SNAKE\", \"camel\", \"kebab\" or \"preserve\" for 'env_case'",
+                                )),
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(ENV_ATTRIBUTE) =>
+                        {
+                            let candidates = match &named_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Str(env), ..
+                                }) => Some(vec![env.value()]),
+                                Expr::Array(array) => array
+                                    .elems
+                                    .iter()
+                                    .map(|elem| match elem {
+                                        Expr::Lit(ExprLit {
+                                            lit: Lit::Str(env), ..
+                                        }) => Some(env.value()),
+                                        _ => None,
+                                    })
+                                    .collect(),
+                                _ => None,
+                            };
+                            match candidates {
+                                Some(candidates) if !candidates.is_empty() => {
+                                    attributes.env = candidates
+                                }
+                                _ => errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string or an array of strings for 'env'",
+                                )),
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(RENAME_ALL_ATTRIBUTE) =>
+                        {
+                            match &named_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Str(rename_all),
+                                    ..
+                                }) if file::RENAME_RULES.contains(&rename_all.value().as_str()) => {
+                                    attributes.rename_all = Some(rename_all.value());
+                                }
+                                _ => errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    format!(
+                                        "Expected one of {} for 'rename_all'",
+                                        file::RENAME_RULES
+                                            .map(|rule| format!("\"{}\"", rule))
+                                            .join(", ")
+                                    ),
+                                )),
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(DEPRECATED_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(deprecated),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.deprecated = Some(deprecated.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a message for 'deprecated'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(SINCE_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(since),
+                                ..
+                            }) = &named_value.value
+                            {
+                                attributes.since = Some(since.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a version string for 'since'",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(named_value)
+                            if named_value.path.is_ident(TAG_ATTRIBUTE) =>
+                        {
+                            if let Expr::Lit(ExprLit {
+                                lit: Lit::Str(tag), ..
+                            }) = &named_value.value
+                            {
+                                attributes.tag = Some(tag.value());
+                            } else {
+                                errors.push(Error::new_spanned(
+                                    named_value.into_token_stream(),
+                                    "Expected a string for 'tag'",
+                                ));
+                            }
+                        }
                         _ => {
                             errors.push(Error::new_spanned(
                                 meta.into_token_stream(),
@@ -239,31 +671,73 @@ pub(crate) fn parse_config_field_attributes(
     }
 }
 
-pub(crate) fn get_ident_from_type(ty: &Type) -> proc_macro2::Ident {
-    if let Type::Path(type_path) = ty {
-        type_path.path.segments.last().unwrap().ident.clone()
-    } else {
-        panic!("Type is not a Type::Path, which is required for nested builder patterns")
-    }
+/// The layer of a nested configuration type, resolved through its [`Confgr`](confgr_core::Confgr)
+/// implementation so that neither the type nor its layer need to be in scope.
+pub(crate) fn nested_layer(ty: &Type) -> proc_macro2::TokenStream {
+    quote! { <#ty as ::confgr::core::Confgr>::Layer }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct ConfigAttributes {
     skip: bool,
     nest: bool,
+    flatten: bool,
+    interpolate: bool,
+    strict: bool,
+    includes: bool,
+    separate_words: bool,
+    env_case_insensitive: bool,
     prefix: Option<String>,
     key: Option<String>,
+    env: Vec<String>,
     separator: Option<String>,
+    env_case: Option<String>,
+    rename_all: Option<String>,
     path: Option<String>,
     env_path: Option<String>,
+    env_json: Option<String>,
     default_path: Option<String>,
     name: Option<String>,
+    dotenv: Option<String>,
+    embed: Option<String>,
+    discover: Option<String>,
+    dir: Option<String>,
+    merge: Option<String>,
+    merge_with: Option<String>,
+    tag: Option<String>,
+    alias: Vec<String>,
+    env_alias: Vec<String>,
+    deprecated: Option<String>,
+    since: Option<String>,
+    doc: Option<String>,
 }
 
 impl ConfigAttributes {
     fn new() -> Self {
         Self::default()
     }
+
+    /// The first set attribute which only has an effect on structs and enums, if any.
+    fn struct_only(&self) -> Option<&'static str> {
+        [
+            (self.interpolate, INTERPOLATE_ATTRIBUTE),
+            (self.strict, STRICT_ATTRIBUTE),
+            (self.includes, INCLUDES_ATTRIBUTE),
+            (self.env_case_insensitive, ENV_CASE_INSENSITIVE_ATTRIBUTE),
+            (self.rename_all.is_some(), RENAME_ALL_ATTRIBUTE),
+            (self.path.is_some(), PATH_ATTRIBUTE),
+            (self.env_path.is_some(), ENV_PATH_ATTRIBUTE),
+            (self.env_json.is_some(), ENV_JSON_ATTRIBUTE),
+            (self.default_path.is_some(), DEFAULT_PATH_ATTRIBUTE),
+            (self.dotenv.is_some(), DOTENV_ATTRIBUTE),
+            (self.embed.is_some(), EMBED_ATTRIBUTE),
+            (self.discover.is_some(), DISCOVER_ATTRIBUTE),
+            (self.dir.is_some(), DIR_ATTRIBUTE),
+            (self.tag.is_some(), TAG_ATTRIBUTE),
+        ]
+        .into_iter()
+        .find_map(|(set, attribute)| set.then_some(attribute))
+    }
 }
 
 fn to_compile_errors(errors: Vec<syn::Error>) -> proc_macro2::TokenStream {
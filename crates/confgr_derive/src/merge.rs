@@ -1,14 +1,21 @@
-use crate::{get_ident_from_type, ConfigAttributes, SUFFIX};
+use crate::{add_predicates, nested_layer, ConfigAttributes, SUFFIX};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Ident, Type};
+use syn::{parse_quote, Generics, Ident, Member, Type};
+
+const DEFAULT_MERGE_STRATEGY: &str = "replace";
+pub(crate) const MERGE_STRATEGIES: &[&str] = &["replace", "append", "union"];
 
 pub fn generate_layer(
     name: &Ident,
+    generics: &Generics,
     attributes: &ConfigAttributes,
-    field_data: &[(&Ident, &Type, ConfigAttributes)],
+    field_data: &[(Member, &Type, ConfigAttributes)],
 ) -> TokenStream {
     let layer_name = format_ident!("{}{}", name, SUFFIX);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let default_generics = add_predicates(generics, [parse_quote! { #name #ty_generics: Default }]);
+    let (_, _, default_where_clause) = default_generics.split_for_impl();
 
     let struct_rename = if let Some(attr_name) = &attributes.name {
         quote! { #[serde(rename = #attr_name)] }
@@ -16,72 +23,64 @@ pub fn generate_layer(
         quote! {}
     };
 
-    let (field_defs, empty_defs, merges) = field_data.iter().fold(
-        (vec![], vec![], vec![]),
-        |(mut defs, mut empty, mut merges), (field_name, ty, attr)| {
-            let field_rename = if let Some(rename) = &attr.name {
-                quote! { #[serde(rename = #rename)] }
-            } else {
-                quote! {}
-            };
+    let rename_all = if let Some(rule) = &attributes.rename_all {
+        quote! { #[serde(rename_all = #rule)] }
+    } else {
+        quote! {}
+    };
 
-            if attr.nest {
-                let ty_ident = get_ident_from_type(ty);
-                let nested_builder = format_ident!("{}{}", ty_ident, SUFFIX);
-                defs.push(quote! {
-                    #field_rename
-                    #[serde(default)]
-                    pub #field_name: #nested_builder
-                });
-                empty.push(quote! {
-                    #field_name: #nested_builder::empty()
-                });
-                merges.push(quote! {
-                    #field_name: self.#field_name.merge(other.#field_name)
-                });
-            } else {
-                defs.push(quote! {
-                    #field_rename
-                    pub #field_name: Option<#ty>
-                });
-                empty.push(quote! {
-                    #field_name: None
-                });
-                merges.push(quote! {
-                    #field_name: self.#field_name.or(other.#field_name)
-                });
+    let (field_defs, empty_defs, merges, fills) = generate_layer_fields(field_data);
+
+    let layer_struct = if is_tuple(field_data) {
+        let transparent = if field_data.len() == 1 {
+            quote! { #[serde(transparent)] }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #transparent
+            pub struct #layer_name #generics (#( #field_defs ),*) #where_clause;
+        }
+    } else {
+        quote! {
+            #rename_all
+            pub struct #layer_name #generics #where_clause {
+                #( #field_defs ),*
             }
-            (defs, empty, merges)
-        },
-    );
+        }
+    };
 
     quote! {
         #[automatically_derived]
         #[derive(::serde::Deserialize, Debug, Clone)]
         #[doc(hidden)]
         #struct_rename
-        pub struct #layer_name {
-            #( #field_defs ),*
-        }
+        #layer_struct
 
         #[automatically_derived]
-        impl Default for #layer_name {
+        impl #impl_generics Default for #layer_name #ty_generics #default_where_clause {
            fn default() -> Self {
-                #name::default().into()
+                <#name #ty_generics as Default>::default().into()
             }
         }
 
         #[automatically_derived]
-        impl ::confgr::core::Merge for #layer_name {
+        impl #impl_generics ::confgr::core::Merge for #layer_name #ty_generics #where_clause {
             fn merge(self, other: Self) -> Self {
                 Self {
                     #( #merges ),*
                 }
             }
+
+            fn fill(self, defaults: Self) -> Self {
+                Self {
+                    #( #fills ),*
+                }
+            }
         }
 
         #[automatically_derived]
-        impl ::confgr::core::Empty for #layer_name {
+        impl #impl_generics ::confgr::core::Empty for #layer_name #ty_generics #where_clause {
             fn empty() -> Self {
                Self {
                 #( #empty_defs ),*
@@ -90,3 +89,81 @@ pub fn generate_layer(
         }
     }
 }
+
+/// Whether the fields belong to a tuple struct, whose layer is a tuple struct as well.
+pub(crate) fn is_tuple(field_data: &[(Member, &Type, ConfigAttributes)]) -> bool {
+    matches!(field_data.first(), Some((Member::Unnamed(_), _, _)))
+}
+
+/// Generates the layer's field definitions, along with the field initializers of its
+/// [`Empty`](confgr_core::Empty) implementation and of the `merge` and `fill` methods of its
+/// [`Merge`](confgr_core::Merge) implementation.
+pub(crate) fn generate_layer_fields(
+    field_data: &[(Member, &Type, ConfigAttributes)],
+) -> (
+    Vec<TokenStream>,
+    Vec<TokenStream>,
+    Vec<TokenStream>,
+    Vec<TokenStream>,
+) {
+    field_data.iter().fold(
+        (vec![], vec![], vec![], vec![]),
+        |(mut defs, mut empty, mut merges, mut fills), (field_name, ty, attr)| {
+            let aliases = &attr.alias;
+            let field_rename = if let Some(rename) = &attr.name {
+                quote! { #[serde(rename = #rename #(, alias = #aliases)*)] }
+            } else {
+                quote! { #( #[serde(alias = #aliases)] )* }
+            };
+            let field_def = match field_name {
+                Member::Named(ident) => quote! { #ident: },
+                Member::Unnamed(_) => quote! {},
+            };
+
+            if attr.nest {
+                let nested_builder = nested_layer(ty);
+                let nested_empty = format!("<{} as ::confgr::core::Empty>::empty", nested_builder);
+                defs.push(quote! {
+                    #field_rename
+                    #[serde(default = #nested_empty)]
+                    pub #field_def #nested_builder
+                });
+                empty.push(quote! {
+                    #field_name: <#nested_builder as ::confgr::core::Empty>::empty()
+                });
+                merges.push(quote! {
+                    #field_name: ::confgr::core::Merge::merge(self.#field_name, other.#field_name)
+                });
+                fills.push(quote! {
+                    #field_name: ::confgr::core::Merge::fill(self.#field_name, defaults.#field_name)
+                });
+            } else {
+                defs.push(quote! {
+                    #field_rename
+                    pub #field_def Option<#ty>
+                });
+                empty.push(quote! {
+                    #field_name: None
+                });
+                let strategy = if let Some(merge_with) = &attr.merge_with {
+                    let merge_with = syn::parse_str::<syn::Path>(merge_with)
+                        .expect("'merge_with' is validated while parsing attributes");
+                    quote! { #merge_with }
+                } else {
+                    let strategy = format_ident!(
+                        "{}",
+                        attr.merge.as_deref().unwrap_or(DEFAULT_MERGE_STRATEGY)
+                    );
+                    quote! { ::confgr::core::strategy::#strategy }
+                };
+                merges.push(quote! {
+                    #field_name: #strategy(self.#field_name, other.#field_name)
+                });
+                fills.push(quote! {
+                    #field_name: self.#field_name.or(defaults.#field_name)
+                });
+            }
+            (defs, empty, merges, fills)
+        },
+    )
+}
@@ -3,8 +3,12 @@
 //! The [`Config`](self::derive::Config) derive macro simplifies application configuration by automatically loading
 //! settings from various sources in the following order:
 //! 1. **Environment Variables**.
-//! 2. **Configuration File** (e.g., `toml`, `json`, `yaml`, `ini`, `ron`, `json5`).
-//! 3. **Default Values**.
+//! 2. **Overrides by Path** (e.g., `APP__SERVICE__URL`), setting any file key of a struct with a `prefix`.
+//! 3. **Dotenv File** (e.g., `.env`), read without modifying the process environment.
+//! 4. **JSON Variable**, a whole document in the variable set by `env_json`.
+//! 5. **Configuration File** (e.g., `toml`, `json`, `yaml`, `ini`, `ron`, `json5`).
+//! 6. **Embedded File**, baked into the binary at compile time.
+//! 7. **Default Values**.
 //!
 //! ## Key Features
 //!
@@ -19,23 +23,133 @@
 //! | `prefix`      | Sets a prefix for environment variables. Can be applied at the struct or field level.                                                      |
 //! | `path`        | Specifies the static path to a configuration file. The file extension may (though probably shouldn't) be omitted.                          |
 //! | `env_path`    | Resolves an environment variable at runtime to determine the configuration file path.                                                      |
+//! | `env_json`    | Reads a whole JSON (or TOML) document from an environment variable, merged above the files and below individual environment variables. Struct level only. |
 //! | `default_path`| Specifies a fallback path used if the path determined by `env_path` does not exist.                                                        |
+//! | `dotenv`      | Specifies the path to a `.env` file whose variables are resolved with the same keys as environment variables. Ignored if it does not exist. |
+//! | `embed`       | Bakes a configuration file into the binary with [`include_str!`], merged just above the default values. Relative to the crate root.       |
+//! | `dir`         | Merges every configuration fragment in a directory (e.g., `conf.d`) in lexical order of their file names.                                 |
+//! | `discover`    | Searches the standard configuration directories for an application's files and merges all of them. See below for the search order.   |
+//! | `interpolate` | Resolves `${ENV}`, `${ENV:-fallback}` and `${section.key}` references in file values before deserialization. `${key}` refers to a top level key if it is set, and to an environment variable otherwise. Struct level only.  |
+//! | `strict`      | Fails loading files with keys that do not match any field, suggesting the closest field name. Use [`set_unknown_keys()`](core::set_unknown_keys) to deny or warn about unknown keys at runtime instead. Struct level only. |
+//! | `includes`    | Lets files include other files through the top level `include` key, which is then reserved. Struct level only. |
 //! | `key`         | Overrides the default environment variable name. This ignores the prefix and uses the provided key directly.                               |
+//! | `env`         | Reads a field from the first set variable of a list, e.g. `env = ["APP_DB_URL", "DATABASE_URL"]`, ignoring the prefix. `get_env_keys()` reports all candidates, separated by commas. Cannot be used with `key`. |
+//! | `alias`       | Accepts an additional, deprecated file key for a field through `#[serde(alias = "_")]`. May be repeated.                                    |
+//! | `env_alias`   | Accepts an additional, deprecated environment variable for a field, used when the current one is not set. May be repeated.             |
+//! | `deprecated`  | Marks a field as deprecated with a message. Setting it from a file or environment variable emits a warning. Not available on nested fields. |
+//! | `since`       | The version a `deprecated` field was deprecated in, included in its warning.                                                          |
+//! | `merge`       | Sets how a field is merged across layers: `"replace"` (default), `"append"` for collections or `"union"` for maps, where higher layers win per key. Default values are only used when no source sets the field. |
+//! | `merge_with`  | Merges a field with a custom function of the form `fn(higher: Option<T>, lower: Option<T>) -> Option<T>`. Cannot be used with `merge`.  |
 //! | `name`        | forwards to `#[serde(rename = "_")]` to rename fields during serialization/deserialization. It does not affect environment variable names. |
-//! | `nest`        | Required for non-standard types which must also derive [`Config`](self::derive::Config), used for nesting configuration structs.           |
+//! | `rename_all`  | Renames every file key of a struct, e.g. `"kebab-case"` to map `max-connections` to `max_connections`, with the same rules as serde. Nested structs without their own `rename_all` follow it as well. Struct level only. |
+//! | `nest`        | Required for non-standard types which must also derive [`Config`](self::derive::Config), used for nesting configuration structs. The type may be referenced by any path, including from other crates. |
+//! | `flatten`     | Like `nest`, but the nested struct's fields appear at the parent's level, both in files (as with serde's `flatten`) and in environment variables, which use the parent's prefix. |
 //! | `skip`        | Skips loading the attribute from an environment variable. Necessary for types that don't implement [`FromStr`](std::str::FromStr) but are present in the configuration file. |
 //! | `separator`   | Specifies a character to separate the prefix and the field name. The default separator is "_".                                             |
+//! | `separate_words` | Also separates the words of multi-word field names with the `separator`, e.g. `APP__LISTEN__ADDR` for `listen_addr`. Can be applied at the struct or field level. |
+//! | `env_case`    | Sets the case of environment variable names: `"SCREAMING_SNAKE"` (default), `"camel"`, `"kebab"` or `"preserve"`. Prefixes, keys and aliases are used as written unless `"SCREAMING_SNAKE"`. Can be applied at the struct or field level. |
+//! | `env_case_insensitive` | Matches environment variables regardless of case, e.g. `app_port` for `APP_PORT`, preferring exact matches. The environment is scanned once instead of looking up every variable. Applies to nested structs as well. Struct level only. |
+//! | `tag`         | Sets the key selecting the variant of an enum, in files and (with the prefix) in environment variables. The default tag is "kind". Enum level only. |
 //!
 //! ## Path Attribute Behavior
 //!
 //! - **`env_path`**: Resolves the provided environment variable into configuration filepath. This
-//! takes precedence over `path` and `default_path`, but will not panic if the file or environment
-//! does not exist.
+//!   takes precedence over `path` and `default_path`, but will not panic if the file or environment
+//!   does not exist.
 //!
 //! - **`path`**: Directly sets the path to the configuration file. When set, `default_path` may not be used. Panics if the file does not exist.
 //!
 //! - **`default_path`**: Identical to `path`, but does not panic if the file does not exist.
 //!
+//! - **`discover`**: Searches `/etc/{name}/config.*`, `$XDG_CONFIG_HOME/{name}/config.*` (or `~/.config/{name}/config.*`
+//!   when `XDG_CONFIG_HOME` is unset) and `./{name}.*`, merging every file found with later locations taking precedence. The file resolved by the
+//!   attributes above is merged next.
+//!
+//! - **`dir`**: Merges every file with a supported extension in the directory last, sorted by file name so that
+//!   `10-base.toml` is overridden by `20-local.yaml`. A missing directory is ignored.
+//!
+//! - **`includes`**: Lets any loaded file include other files with `include = ["common.toml", "secrets.toml"]`.
+//!   Included paths are resolved relative to the including file and are overridden by it. Include cycles are
+//!   reported as [`ConfgrError::IncludeCycle`](core::ConfgrError::IncludeCycle) along with the include chain.
+//!
+//! ## Enums and Newtypes
+//!
+//! Enums with named or unit variants may derive [`Config`](self::derive::Config) to select one of several mutually
+//! exclusive configurations. The variant is chosen by the tag key, `kind = "local"`, or by the tag environment
+//! variable, `APP_STORAGE_KIND=local`. Tags are the snake case variant names, matched case-insensitively, and can
+//! be renamed with `name` on the variant. A plain string, `storage = "memory"`, selects a variant without setting
+//! any of its fields. Fields of the selected variant that no layer sets take the [`Default`] of their
+//! type.
+//!
+//! ```rust
+//! # use confgr::prelude::*;
+//! #[derive(Config, Debug, PartialEq)]
+//! #[config(prefix = "APP_STORAGE")]
+//! enum Storage {
+//!     S3 { bucket: String, region: String },
+//!     Local { path: String },
+//!     Memory,
+//! }
+//!
+//! impl Default for Storage {
+//!     fn default() -> Self {
+//!         Self::Memory
+//!     }
+//! }
+//!
+//! let storage = Storage::load_config_with_env(|key| match key {
+//!     "APP_STORAGE_KIND" => Some("local".to_string()),
+//!     "APP_STORAGE_PATH" => Some("/srv".to_string()),
+//!     _ => None,
+//! });
+//!
+//! assert_eq!(storage, Storage::Local { path: "/srv".to_string() });
+//! ```
+//!
+//! Tuple structs are supported as well. The field of a newtype struct is read from the prefix itself, e.g.
+//! `struct Port(u16)` with `prefix = "APP_PORT"` is read from `APP_PORT`, while the fields of other tuple structs
+//! are read from `{prefix}_{index}`.
+//!
+//! ## Aliases, Deprecations and Warnings
+//!
+//! Settings that are renamed can keep accepting their old names with `alias` and `env_alias`. Whenever an alias
+//! is used, a [`Warning`](core::Warning) with the current name of the setting, its source and a message is passed
+//! to the hook registered with [`set_warning_hook()`](core::set_warning_hook), e.g. to log it.
+//!
+//! Fields marked with `#[config(deprecated = "use `listen_addr` instead", since = "2.0")]` emit a warning as well
+//! whenever they are set. [`load_config_with_warnings()`](core::Confgr::load_config_with_warnings) returns the
+//! warnings emitted while loading along with the configuration, and the `log` and `tracing` features forward every
+//! warning to the respective crate.
+//!
+//! ```rust
+//! confgr::core::set_warning_hook(|warning| eprintln!("warning: {}", warning));
+//! ```
+//!
+//! ## Overriding Keys by Path
+//!
+//! Besides the variable of each field, any file key of a struct with a `prefix` can be overridden by a variable
+//! made of the prefix and the key's segments separated by double underscores, e.g. `APP__SERVICE__URL` for
+//! `service.url` or `APP__PARAMETERS__RETRIES` for an entry of a skipped `HashMap` field. Segments are lowercased,
+//! the prefix is matched regardless of case with `env_case_insensitive`, and the variables of single fields take
+//! precedence over such overrides.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use confgr::prelude::*;
+//!
+//! #[derive(Config, Default)]
+//! #[config(prefix = "APP")]
+//! pub struct AppConfig {
+//!     #[config(skip)]
+//!     parameters: HashMap<String, u32>,
+//! }
+//!
+//! let vars = HashMap::from([("APP__PARAMETERS__RETRIES".to_string(), "3".to_string())]);
+//! let settings = AppConfig::load_config_with_env_map(&vars);
+//!
+//! assert_eq!(settings.parameters["retries"], 3);
+//! ```
+//!
 //! ## Usage
 //!
 //! <br/>
@@ -92,13 +206,22 @@
 //! ## Warnings/Pitfalls
 //!
 //! - Nested structs do not load separate files based on their own `path` attributes. If
-//! you would like multiple files to be loaded, you must use multiple structs with multiple
-//! [`load_config()`](core::Confgr::load_config()) calls. This may change in a future version.
+//!   you would like multiple files to be loaded, you must use multiple structs with multiple
+//!   [`load_config()`](core::Confgr::load_config()) calls. This may change in a future version.
 //! - Types that do not implement [`FromStr`](std::str::FromStr) must use `#[config(skip)]` or `#[config(nest)]`.
 //! - The `separator` character is only inserted between the prefix and the field name, not in any
-//! part of the parsed field name.
+//!   part of the parsed field name.
 //! - The `prefix` is applied per field or for the entire struct, but is ignored if `#[config(key = "_")]` is used.
+//! - `load_config()` skips sources that fail to load, such as a malformed file, and panics instead in `strict`
+//!   mode. Use [`try_load_config()`](core::Confgr::try_load_config) to handle the error.
+//! - With `includes`, the top level `include` key of configuration files is reserved and cannot be used as a field name.
 //! - All configuration structs must implement [`Default`].
+//! - Generic structs are supported, but fields of a generic type are only read from environment variables when the
+//!   type implements [`FromStr`](std::str::FromStr), and [`PhantomData`](std::marker::PhantomData) fields never are.
+//!   Borrowed fields such as `&'a str` cannot be loaded. Generic enums are not supported.
+//! - Fields of enum variants that are not set by any layer fall back to their type's [`Default`], unless the
+//!   variant is the enum's default, so their types must implement [`Default`]. Selecting `kind = "s3"` without
+//!   a `bucket` yields an empty bucket rather than an error. Tuple variants are not supported.
 //! - Types used in configuration structs must implement [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html), [`Clone`], [`Debug`] and [`Default`].
 //! - [`Option`] is not currently compatible with `#[config(nest)]` on types that implement [`Confgr`](self::core::Confgr).
 //!
@@ -132,6 +255,56 @@
 //! assert_eq!(keys["debug"], "DEBUG_MODE");
 //! ```
 //!
+//! [`env_schema()`](core::Confgr::env_schema) returns the same variables as a tree of
+//! [`EnvKey`](core::EnvKey)s, along with the type, default value and doc comment of each field,
+//! recursing into nested fields, e.g. to render deployment docs.
+//!
+//! ### Isolating Environment Variables
+//!
+//! [`load_config_with_env()`](core::Confgr::load_config_with_env) resolves environment keys through
+//! the provided closure instead of the process environment, which keeps tests from racing on
+//! global state. This includes the `env_path` variable and the `XDG_CONFIG_HOME` and `HOME`
+//! variables searched by `discover`. [`FromEnv::from_env_map()`](core::FromEnv::from_env_map) does the same for a single layer.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use confgr::prelude::*;
+//!
+//! #[derive(Config, Default)]
+//! #[config(prefix = "APP")]
+//! pub struct AppConfig {
+//!     port: u32,
+//! }
+//!
+//! let vars = HashMap::from([("APP_PORT".to_string(), "8080".to_string())]);
+//! let settings = AppConfig::load_config_with_env(|key| vars.get(key).cloned());
+//!
+//! assert_eq!(settings.port, 8080);
+//! ```
+//!
+//! ### Detecting Unknown Environment Variables
+//!
+//! [`find_unknown_env_vars()`](core::Confgr::find_unknown_env_vars) scans the environment for
+//! variables starting with the prefix of the struct or any nested struct that do not match any
+//! field, along with the closest known variable, so that typos in deployment manifests surface at
+//! startup. [`FromEnv::find_unknown_env_vars_in()`](core::FromEnv::find_unknown_env_vars_in)
+//! checks a given list of names instead.
+//!
+//! ```rust
+//! use confgr::prelude::*;
+//!
+//! #[derive(Config, Default)]
+//! #[config(prefix = "APP")]
+//! pub struct AppConfig {
+//!     port: u32,
+//! }
+//!
+//! let unknown = <AppConfig as Confgr>::Layer::find_unknown_env_vars_in(["APP_PROTT", "APP_PORT", "PATH"]);
+//!
+//! assert_eq!(unknown[0].key, "APP_PROTT");
+//! assert_eq!(unknown[0].suggestion.as_deref(), Some("APP_PORT"));
+//! ```
+//!
 //! ### Verifying Configuration File Path
 //!
 //! You can use [`check_file()`](core::Confgr::check_file) to ensure that the configuration file
@@ -167,6 +340,9 @@
 //!
 //! The [`deserialize_from_file()`](core::Confgr::deserialize_from_file()) method can be used to manually test the config deserialization step. This
 //! will give you the parsed configuration struct before default values are applied.
+//! Contents that are not on disk can be tested the same way with [`FromFile::from_str()`](core::FromFile::from_str)
+//! and [`FromFile::from_reader()`](core::FromFile::from_reader), or loaded in place of the file with
+//! [`load_config_from_str()`](core::Confgr::load_config_from_str).
 //!
 //! ```rust
 //! use confgr::prelude::*;
@@ -200,11 +376,11 @@ pub mod derive {
 
 #[doc(hidden)]
 pub mod config {
-    pub use config::{Config, ConfigError, File};
+    pub use config::{Config, ConfigError, File, Value, ValueKind};
 }
 
 /// Macro and trait exports for convenience.
 pub mod prelude {
-    pub use crate::core::{Confgr, Empty, FromEnv, FromFile, Merge};
+    pub use crate::core::{Confgr, Empty, Format, FromDotenv, FromEnv, FromFile, Merge};
     pub use crate::derive::Config;
 }
@@ -0,0 +1,62 @@
+use confgr::core::{set_warning_hook, Source, Warning};
+use confgr::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Config, Debug)]
+#[config(prefix = "ALIAS")]
+pub struct ServerConfig {
+    #[config(alias = "listen", alias = "bind", env_alias = "ALIAS_LISTEN")]
+    pub listen_addr: String,
+    #[config(env_alias = "ALIAS_WORKERS")]
+    pub threads: u32,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: "127.0.0.1:80".to_string(),
+            threads: 1,
+        }
+    }
+}
+
+#[derive(Config, Debug, Default)]
+pub struct AliasConfig {
+    #[config(nest, alias = "http")]
+    pub server: ServerConfig,
+}
+
+#[test]
+fn test_file_and_env_aliases() {
+    let warnings = Arc::new(Mutex::new(Vec::<Warning>::new()));
+    let collected = warnings.clone();
+    set_warning_hook(move |warning| collected.lock().unwrap().push(warning.clone()));
+
+    let layer = <AliasConfig as Confgr>::Layer::from_str(
+        r#"
+        [http]
+        bind = "0.0.0.0:8080"
+        "#,
+        Format::Toml,
+    )
+    .unwrap();
+    assert_eq!(layer.server.listen_addr, Some("0.0.0.0:8080".to_string()));
+
+    let vars = HashMap::from([
+        ("ALIAS_LISTEN".to_string(), "10.0.0.1:80".to_string()),
+        ("ALIAS_THREADS".to_string(), "8".to_string()),
+        ("ALIAS_WORKERS".to_string(), "4".to_string()),
+    ]);
+    let layer = <ServerConfig as Confgr>::Layer::from_env_map(&vars);
+    assert_eq!(layer.listen_addr, Some("10.0.0.1:80".to_string()));
+    assert_eq!(layer.threads, Some(8));
+
+    let warnings = warnings.lock().unwrap();
+    assert_eq!(warnings.len(), 3);
+    assert_eq!(warnings[0].field, "server");
+    assert_eq!(warnings[1].field, "server.listen_addr");
+    assert_eq!(warnings[1].source, Source::File);
+    assert_eq!(warnings[2].field, "ALIAS_LISTEN_ADDR");
+    assert_eq!(warnings[2].source, Source::Env);
+}
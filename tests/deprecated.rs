@@ -0,0 +1,55 @@
+use confgr::core::Source;
+use confgr::prelude::*;
+
+use std::fs::File;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(path = "tests/common/deprecated.toml", prefix = "DEPRECATED")]
+pub struct DeprecatedConfig {
+    pub listen_addr: String,
+    #[config(deprecated = "use `listen_addr` instead", since = "2.0")]
+    pub port: u16,
+    #[config(deprecated = "workers are sized automatically")]
+    pub workers: u32,
+}
+
+impl Default for DeprecatedConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: "127.0.0.1:80".to_string(),
+            port: 80,
+            workers: 1,
+        }
+    }
+}
+
+#[test]
+fn test_deprecated_field_warnings() {
+    let mut file = File::create("tests/common/deprecated.toml").unwrap();
+    writeln!(file, "port = 8080").unwrap();
+    std::env::set_var("DEPRECATED_WORKERS", "4");
+
+    let (config, warnings) = DeprecatedConfig::load_config_with_warnings();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.workers, 4);
+    assert_eq!(warnings.len(), 2);
+
+    assert_eq!(warnings[0].field, "port");
+    assert_eq!(warnings[0].source, Source::File);
+    assert_eq!(
+        warnings[0].message,
+        "deprecated since 2.0: use `listen_addr` instead"
+    );
+
+    assert_eq!(warnings[1].field, "DEPRECATED_WORKERS");
+    assert_eq!(warnings[1].source, Source::Env);
+    assert_eq!(
+        warnings[1].message,
+        "deprecated: workers are sized automatically"
+    );
+
+    std::env::remove_var("DEPRECATED_WORKERS");
+    std::fs::remove_file("tests/common/deprecated.toml").unwrap();
+}
@@ -0,0 +1,93 @@
+use confgr::prelude::*;
+
+use std::fs;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(discover = "confgr_discover_test", prefix = "DISCOVER")]
+pub struct DiscoverConfig {
+    pub name: String,
+    pub port: u32,
+    pub timeout: u64,
+}
+
+impl Default for DiscoverConfig {
+    fn default() -> Self {
+        Self {
+            name: "DefaultName".to_string(),
+            port: 3000,
+            timeout: 100,
+        }
+    }
+}
+
+fn write_file(path: &std::path::Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut file = fs::File::create(path).unwrap();
+    writeln!(file, "{}", contents).unwrap();
+}
+
+#[test]
+fn test_discovered_files_are_merged() {
+    let config_home = tempfile::tempdir().unwrap();
+    let home = tempfile::tempdir().unwrap();
+    let original_home = std::env::var_os("HOME");
+    std::env::set_var("XDG_CONFIG_HOME", config_home.path());
+    std::env::set_var("HOME", home.path());
+
+    // Ignored because `XDG_CONFIG_HOME` takes the place of `~/.config`.
+    write_file(
+        &home.path().join(".config/confgr_discover_test/config.toml"),
+        "port = 9999",
+    );
+
+    write_file(
+        &config_home.path().join("confgr_discover_test/config.toml"),
+        "name = \"UserName\"\ntimeout = 400",
+    );
+    write_file(
+        std::path::Path::new("confgr_discover_test.yaml"),
+        "name: LocalName",
+    );
+
+    let paths = DiscoverConfig::get_file_paths();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[1], "confgr_discover_test.yaml");
+
+    let config = DiscoverConfig::load_config();
+
+    assert_eq!(config.name, "LocalName");
+    assert_eq!(config.timeout, 400);
+    assert_eq!(config.port, 3000);
+
+    fs::remove_file("confgr_discover_test.yaml").unwrap();
+    std::env::remove_var("XDG_CONFIG_HOME");
+    if let Some(original_home) = original_home {
+        std::env::set_var("HOME", original_home);
+    }
+}
+
+#[derive(Config, Default)]
+#[config(discover = "confgr_discover_lookup_test")]
+pub struct DiscoverLookupConfig {
+    pub name: String,
+}
+
+#[test]
+fn test_discover_reads_config_home_from_lookup() {
+    let config_home = tempfile::tempdir().unwrap();
+    write_file(
+        &config_home
+            .path()
+            .join("confgr_discover_lookup_test/config.toml"),
+        "name = \"LookupName\"",
+    );
+
+    let vars = std::collections::HashMap::from([(
+        "XDG_CONFIG_HOME".to_string(),
+        config_home.path().display().to_string(),
+    )]);
+    let config = DiscoverLookupConfig::load_config_with_env_map(&vars);
+
+    assert_eq!(config.name, "LookupName");
+}
@@ -0,0 +1,68 @@
+use confgr::prelude::*;
+
+use std::fs::File;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(
+    path = "tests/common/dotenv.toml",
+    dotenv = "tests/common/test.env",
+    prefix = "DOTENV"
+)]
+pub struct DotenvConfig {
+    pub name: String,
+    pub port: u32,
+    pub timeout: u64,
+    #[config(skip)]
+    pub skipped: bool,
+}
+
+impl Default for DotenvConfig {
+    fn default() -> Self {
+        Self {
+            name: "DefaultName".to_string(),
+            port: 3000,
+            timeout: 100,
+            skipped: false,
+        }
+    }
+}
+
+fn setup_files() {
+    let mut env_file = File::create("tests/common/test.env").unwrap();
+    writeln!(
+        env_file,
+        "# comment\nDOTENV_NAME=DotenvName\nDOTENV_PORT=4000\nDOTENV_SKIPPED=true"
+    )
+    .unwrap();
+
+    let mut config_file = File::create("tests/common/dotenv.toml").unwrap();
+    writeln!(
+        config_file,
+        "name = \"TomlName\"\nport = 5000\ntimeout = 400"
+    )
+    .unwrap();
+}
+
+fn cleanup_files() {
+    let _ = std::fs::remove_file("tests/common/test.env");
+    let _ = std::fs::remove_file("tests/common/dotenv.toml");
+}
+
+#[test]
+fn test_dotenv_priority() {
+    setup_files();
+    std::env::set_var("DOTENV_PORT", "6000");
+
+    let config = DotenvConfig::load_config();
+
+    assert_eq!(config.name, "DotenvName");
+    assert_eq!(config.port, 6000);
+    assert_eq!(config.timeout, 400);
+    assert!(!config.skipped);
+
+    assert!(std::env::var("DOTENV_NAME").is_err());
+
+    std::env::remove_var("DOTENV_PORT");
+    cleanup_files();
+}
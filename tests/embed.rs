@@ -0,0 +1,71 @@
+use confgr::prelude::*;
+
+use std::fs::File;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(
+    embed = "tests/common/embedded.toml",
+    default_path = "tests/common/embed_override.toml",
+    prefix = "EMBED"
+)]
+pub struct EmbedConfig {
+    pub name: String,
+    pub port: u32,
+    pub timeout: u64,
+    pub retries: u32,
+    #[config(nest)]
+    pub database: EmbedDatabase,
+}
+
+impl Default for EmbedConfig {
+    fn default() -> Self {
+        Self {
+            name: "DefaultName".to_string(),
+            port: 3000,
+            timeout: 100,
+            retries: 3,
+            database: EmbedDatabase::default(),
+        }
+    }
+}
+
+#[derive(Config, Debug)]
+pub struct EmbedDatabase {
+    #[config(skip)]
+    pub host: String,
+}
+
+impl Default for EmbedDatabase {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_embedded_priority() {
+    let mut file = File::create("tests/common/embed_override.toml").unwrap();
+    writeln!(file, "port = 5000").unwrap();
+    std::env::set_var("EMBED_TIMEOUT", "600");
+
+    let config = EmbedConfig::load_config();
+
+    assert_eq!(config.name, "EmbeddedName");
+    assert_eq!(config.port, 5000);
+    assert_eq!(config.timeout, 600);
+    assert_eq!(config.retries, 3);
+    assert_eq!(config.database.host, "embedded.local");
+
+    std::env::remove_var("EMBED_TIMEOUT");
+    std::fs::remove_file("tests/common/embed_override.toml").unwrap();
+}
+
+#[test]
+fn test_deserialize_from_embedded() {
+    let layer = EmbedConfig::deserialize_from_embedded().expect("Failed to deserialize layer");
+
+    assert_eq!(layer.port, Some(4000));
+    assert_eq!(layer.retries, None);
+}
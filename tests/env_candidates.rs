@@ -0,0 +1,35 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "APP")]
+pub struct PlatformConfig {
+    #[config(env = ["APP_DB_URL", "DATABASE_URL"])]
+    pub database_url: String,
+    #[config(env = ["APP_PORT", "PORT"])]
+    pub port: u16,
+    pub workers: u32,
+}
+
+#[test]
+fn test_env_candidates() {
+    let vars = HashMap::from([
+        ("DATABASE_URL".to_string(), "postgres://heroku".to_string()),
+        ("APP_PORT".to_string(), "8080".to_string()),
+        ("PORT".to_string(), "5000".to_string()),
+    ]);
+    let layer = <PlatformConfig as Confgr>::Layer::from_env_map(&vars);
+
+    assert_eq!(layer.database_url, Some("postgres://heroku".to_string()));
+    assert_eq!(layer.port, Some(8080));
+    assert_eq!(layer.workers, None);
+}
+
+#[test]
+fn test_env_candidates_keys() {
+    let keys = PlatformConfig::get_env_keys();
+
+    assert_eq!(keys["database_url"], "APP_DB_URL, DATABASE_URL");
+    assert_eq!(keys["port"], "APP_PORT, PORT");
+    assert_eq!(keys["workers"], "APP_WORKERS");
+}
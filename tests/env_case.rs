@@ -0,0 +1,42 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "legacy", env_case = "camel")]
+pub struct CamelConfig {
+    pub listen_addr: String,
+    #[config(env_case = "kebab")]
+    pub max_connections: u32,
+    #[config(env_case = "preserve", key = "Legacy.Timeout")]
+    pub timeout: u64,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "APP", separator = "__", separate_words)]
+pub struct SeparatedConfig {
+    pub listen_addr: String,
+    pub port: u16,
+}
+
+#[test]
+fn test_env_case() {
+    let keys = CamelConfig::get_env_keys();
+    assert_eq!(keys["listen_addr"], "legacy_listenAddr");
+    assert_eq!(keys["max_connections"], "legacy_max-connections");
+    assert_eq!(keys["timeout"], "Legacy.Timeout");
+
+    let vars = HashMap::from([
+        ("legacy_listenAddr".to_string(), "0.0.0.0:80".to_string()),
+        ("legacy_max-connections".to_string(), "16".to_string()),
+    ]);
+    let layer = <CamelConfig as Confgr>::Layer::from_env_map(&vars);
+    assert_eq!(layer.listen_addr, Some("0.0.0.0:80".to_string()));
+    assert_eq!(layer.max_connections, Some(16));
+}
+
+#[test]
+fn test_separate_words() {
+    let keys = SeparatedConfig::get_env_keys();
+    assert_eq!(keys["listen_addr"], "APP__LISTEN__ADDR");
+    assert_eq!(keys["port"], "APP__PORT");
+}
@@ -0,0 +1,46 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "INSENSITIVE", env_case_insensitive)]
+pub struct InsensitiveConfig {
+    pub port: u16,
+    pub host: String,
+    pub debug: bool,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "SENSITIVE")]
+pub struct SensitiveConfig {
+    pub port: u16,
+}
+
+#[test]
+fn test_env_case_insensitive() {
+    let vars = HashMap::from([
+        ("insensitive_port".to_string(), "8080".to_string()),
+        ("Insensitive_Host".to_string(), "localhost".to_string()),
+        ("insensitive_debug".to_string(), "false".to_string()),
+        ("INSENSITIVE_DEBUG".to_string(), "true".to_string()),
+    ]);
+    let layer = <InsensitiveConfig as Confgr>::Layer::from_env_map(&vars);
+
+    assert_eq!(layer.port, Some(8080));
+    assert_eq!(layer.host, Some("localhost".to_string()));
+    assert_eq!(layer.debug, Some(true));
+
+    let vars = HashMap::from([("sensitive_port".to_string(), "8080".to_string())]);
+    let layer = <SensitiveConfig as Confgr>::Layer::from_env_map(&vars);
+    assert_eq!(layer.port, None);
+}
+
+#[test]
+fn test_env_case_insensitive_process_env() {
+    std::env::set_var("insensitive_Port", "9090");
+
+    let layer = <InsensitiveConfig as Confgr>::Layer::from_env();
+    assert_eq!(layer.port, Some(9090));
+    assert!(InsensitiveConfig::find_unknown_env_vars().is_empty());
+
+    std::env::remove_var("insensitive_Port");
+}
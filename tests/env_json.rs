@@ -0,0 +1,52 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, Default)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub pool: u32,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "BLOB", env_json = "BLOB_CONFIG_JSON")]
+pub struct BlobConfig {
+    pub port: u16,
+    pub host: String,
+    #[config(nest)]
+    pub database: DatabaseConfig,
+}
+
+#[test]
+fn test_env_json_layer() {
+    let vars = HashMap::from([
+        (
+            "BLOB_CONFIG_JSON".to_string(),
+            r#"{ "port": 8080, "host": "blob", "database": { "url": "postgres://blob" } }"#
+                .to_string(),
+        ),
+        ("BLOB_HOST".to_string(), "env".to_string()),
+    ]);
+    let config = BlobConfig::load_config_with_env(|key| vars.get(key).cloned());
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.host, "env");
+    assert_eq!(config.database.url, "postgres://blob");
+    assert_eq!(config.database.pool, 0);
+}
+
+#[test]
+fn test_env_json_toml() {
+    let vars = HashMap::from([(
+        "BLOB_CONFIG_JSON".to_string(),
+        "port = 9090\n[database]\npool = 4".to_string(),
+    )]);
+    let layer = <BlobConfig as Confgr>::Layer::from_env_json_with(|key| vars.get(key).cloned())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(layer.port, Some(9090));
+    assert_eq!(layer.database.pool, Some(4));
+    assert!(<BlobConfig as Confgr>::Layer::from_env_json_with(|_| None)
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,72 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Default)]
+#[config(prefix = "MAP_NESTED")]
+struct MapNested {
+    detail: String,
+}
+
+#[derive(Config, Default)]
+#[config(prefix = "MAP")]
+struct MapTest {
+    id: i32,
+    #[config(key = "MAP_CUSTOM_NAME")]
+    name: String,
+    #[config(skip)]
+    skipped: bool,
+    #[config(nest)]
+    nested: MapNested,
+}
+
+#[derive(Config, Default)]
+#[config(env_path = "MAP_CONFIG_PATH")]
+struct MapPathTest {
+    name: String,
+}
+
+fn vars() -> HashMap<String, String> {
+    HashMap::from([
+        ("MAP_ID".to_string(), "42".to_string()),
+        ("MAP_CUSTOM_NAME".to_string(), "MapName".to_string()),
+        ("MAP_SKIPPED".to_string(), "true".to_string()),
+        ("MAP_NESTED_DETAIL".to_string(), "NestedDetail".to_string()),
+    ])
+}
+
+#[test]
+fn test_layer_from_env_map() {
+    let layer = <MapTest as Confgr>::Layer::from_env_map(&vars());
+
+    assert_eq!(layer.id, Some(42));
+    assert_eq!(layer.name, Some("MapName".to_string()));
+    assert_eq!(layer.skipped, None);
+    assert_eq!(layer.nested.detail, Some("NestedDetail".to_string()));
+}
+
+#[test]
+fn test_load_config_with_env_ignores_process_env() {
+    std::env::set_var("MAP_ID", "7");
+
+    let vars = vars();
+    let config = MapTest::load_config_with_env(|key| vars.get(key).cloned());
+
+    assert_eq!(config.id, 42);
+    assert_eq!(config.name, "MapName");
+    assert!(!config.skipped);
+    assert_eq!(config.nested.detail, "NestedDetail");
+
+    std::env::remove_var("MAP_ID");
+}
+
+#[test]
+fn test_load_config_with_env_reads_env_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("map.toml");
+    std::fs::write(&path, "name = \"FromFile\"").unwrap();
+
+    let vars = HashMap::from([("MAP_CONFIG_PATH".to_string(), path.display().to_string())]);
+    let config = MapPathTest::load_config_with_env(|key| vars.get(key).cloned());
+
+    assert_eq!(config.name, "FromFile");
+}
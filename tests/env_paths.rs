@@ -0,0 +1,124 @@
+use confgr::core::ConfgrError;
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, Default)]
+pub struct ServiceConfig {
+    pub url: String,
+    pub timeout: u64,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "PATHS")]
+pub struct PathsConfig {
+    pub port: u16,
+    #[config(nest)]
+    pub service: ServiceConfig,
+    #[config(skip)]
+    pub parameters: HashMap<String, u32>,
+}
+
+#[derive(Config, Debug, Default)]
+pub struct LimitsConfig {
+    pub level: u8,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "FLATPATHS", env_case_insensitive, strict)]
+pub struct FlatPathsConfig {
+    pub port: u16,
+    #[config(flatten)]
+    pub limits: LimitsConfig,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "SEPPATHS", separator = "__", separate_words, strict)]
+pub struct SeparatedPathsConfig {
+    pub listen_addr: String,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "KEBABPATHS", rename_all = "kebab-case")]
+pub struct KebabPathsConfig {
+    pub max_connections: u32,
+    #[config(nest)]
+    pub service: ServiceConfig,
+}
+
+#[test]
+fn test_env_path_overrides() {
+    let vars = HashMap::from([
+        (
+            "PATHS__SERVICE__URL".to_string(),
+            "http://override".to_string(),
+        ),
+        ("PATHS__SERVICE__TIMEOUT".to_string(), "30".to_string()),
+        ("PATHS__PARAMETERS__RETRIES".to_string(), "3".to_string()),
+        ("PATHS__PORT".to_string(), "8080".to_string()),
+        ("PATHS_PORT".to_string(), "9090".to_string()),
+    ]);
+    let config = PathsConfig::load_config_with_env_map(&vars);
+
+    assert_eq!(config.service.url, "http://override");
+    assert_eq!(config.service.timeout, 30);
+    assert_eq!(config.parameters["retries"], 3);
+    assert_eq!(config.port, 9090);
+}
+
+#[test]
+fn test_env_path_overrides_are_known() {
+    std::env::set_var("PATHS__SERVICE__URL", "http://override");
+    std::env::set_var("PATHS__SERVICE__URI", "http://typo");
+
+    let unknown = PathsConfig::find_unknown_env_vars();
+    assert!(!unknown.iter().any(|key| key.key == "PATHS__SERVICE__URL"));
+    assert!(unknown.iter().any(|key| key.key == "PATHS__SERVICE__URI"));
+
+    std::env::remove_var("PATHS__SERVICE__URL");
+    std::env::remove_var("PATHS__SERVICE__URI");
+}
+
+#[test]
+fn test_env_path_overrides_flattened() {
+    let vars = HashMap::from([
+        ("FLATPATHS__PORT".to_string(), "8".to_string()),
+        ("flatpaths__level".to_string(), "7".to_string()),
+    ]);
+    let config = FlatPathsConfig::try_load_config_with_env_map(&vars).unwrap();
+
+    assert_eq!(config.port, 8);
+    assert_eq!(config.limits.level, 7);
+
+    let vars = HashMap::from([("FLATPATHS__LEVLE".to_string(), "7".to_string())]);
+    assert!(matches!(
+        FlatPathsConfig::try_load_config_with_env_map(&vars),
+        Err(ConfgrError::UnknownKeys(_))
+    ));
+
+    std::env::set_var("flatpaths__level", "7");
+    let unknown = FlatPathsConfig::find_unknown_env_vars();
+    std::env::remove_var("flatpaths__level");
+
+    assert!(unknown.is_empty(), "{:?}", unknown);
+}
+
+#[test]
+fn test_env_path_overrides_match_field_keys() {
+    let vars = HashMap::from([("SEPPATHS__LISTEN__ADDR".to_string(), "0.0.0.0".to_string())]);
+    let config = SeparatedPathsConfig::try_load_config_with_env_map(&vars).unwrap();
+    assert_eq!(config.listen_addr, "0.0.0.0");
+
+    let vars = HashMap::from([
+        ("KEBABPATHS__MAX_CONNECTIONS".to_string(), "7".to_string()),
+        ("KEBABPATHS__SERVICE__TIMEOUT".to_string(), "30".to_string()),
+    ]);
+    let config = KebabPathsConfig::try_load_config_with_env_map(&vars).unwrap();
+    assert_eq!(config.max_connections, 7);
+    assert_eq!(config.service.timeout, 30);
+
+    std::env::set_var("KEBABPATHS__MAX_CONNECTIONS", "7");
+    let unknown = KebabPathsConfig::find_unknown_env_vars();
+    std::env::remove_var("KEBABPATHS__MAX_CONNECTIONS");
+
+    assert!(unknown.is_empty(), "{:?}", unknown);
+}
@@ -0,0 +1,86 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Config, Debug)]
+#[config(prefix = "DB")]
+pub struct DatabaseConfig {
+    /// Connection string of the primary database.
+    pub url: String,
+    pub pool: u32,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "postgres://localhost".to_string(),
+            pool: 4,
+        }
+    }
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "SCHEMA")]
+pub struct SchemaConfig {
+    /// Port to listen on.
+    ///
+    /// Ports below 1024 require privileges.
+    pub port: u16,
+    #[config(skip)]
+    pub labels: HashMap<String, String>,
+    #[config(nest)]
+    pub database: DatabaseConfig,
+}
+
+#[derive(Config, Default)]
+pub struct GenericSchemaConfig<S: FromStr + Clone> {
+    #[config(env = ["GEN_SIZE", "POOL_SIZE"])]
+    pub size: S,
+    pub name: String,
+}
+
+#[test]
+fn test_env_schema() {
+    let schema = SchemaConfig::env_schema();
+    assert_eq!(schema.len(), 3);
+
+    let port = &schema[0];
+    assert_eq!(port.field_path, "port");
+    assert_eq!(port.var_names, ["SCHEMA_PORT"]);
+    assert_eq!(port.type_name, "u16");
+    assert!(!port.skipped);
+    assert_eq!(port.default_repr.as_deref(), Some("0"));
+    assert_eq!(
+        port.doc.as_deref(),
+        Some("Port to listen on.\n\nPorts below 1024 require privileges.")
+    );
+
+    let labels = &schema[1];
+    assert_eq!(labels.type_name, "HashMap<String, String>");
+    assert!(labels.skipped);
+    assert!(labels.var_names.is_empty());
+
+    let database = &schema[2];
+    assert!(database.var_names.is_empty());
+    assert_eq!(database.children.len(), 2);
+    assert_eq!(database.children[0].field_path, "database.url");
+    assert_eq!(database.children[0].var_names, ["DB_URL"]);
+    assert_eq!(
+        database.children[0].default_repr.as_deref(),
+        Some("\"postgres://localhost\"")
+    );
+    assert_eq!(
+        database.children[0].doc.as_deref(),
+        Some("Connection string of the primary database.")
+    );
+    assert_eq!(database.children[1].default_repr.as_deref(), Some("4"));
+}
+
+#[test]
+fn test_env_schema_generic() {
+    let schema = GenericSchemaConfig::<u32>::env_schema();
+
+    assert_eq!(schema[0].var_names, ["GEN_SIZE", "POOL_SIZE"]);
+    assert_eq!(schema[0].default_repr, None);
+    assert_eq!(schema[1].default_repr.as_deref(), Some("\"\""));
+}
@@ -0,0 +1,92 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug)]
+#[config(prefix = "COMMON")]
+pub struct CommonServiceConfig {
+    pub log_level: String,
+    pub metrics: bool,
+}
+
+impl Default for CommonServiceConfig {
+    fn default() -> Self {
+        Self {
+            log_level: "info".to_string(),
+            metrics: false,
+        }
+    }
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "FLAT")]
+pub struct ServiceConfig {
+    pub port: u16,
+    #[config(flatten)]
+    pub common: CommonServiceConfig,
+}
+
+#[test]
+fn test_flatten_from_file_and_env() {
+    let vars = HashMap::from([
+        ("FLAT_METRICS".to_string(), "true".to_string()),
+        ("COMMON_LOG_LEVEL".to_string(), "trace".to_string()),
+    ]);
+    let env_layer = <ServiceConfig as Confgr>::Layer::from_env_map(&vars);
+    let file_layer = <ServiceConfig as Confgr>::Layer::from_str(
+        r#"
+        port = 8080
+        log_level = "debug"
+        "#,
+        Format::Toml,
+    )
+    .unwrap();
+
+    let config = ServiceConfig::from(env_layer.merge(file_layer).merge(Default::default()));
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.common.log_level, "debug");
+    assert!(config.common.metrics);
+
+    let env_keys = ServiceConfig::get_env_keys();
+    assert_eq!(env_keys["log_level"], "FLAT_LOG_LEVEL");
+    assert_eq!(env_keys["port"], "FLAT_PORT");
+}
+
+#[derive(Config, Debug, Default)]
+pub struct LimitsConfig {
+    pub level: u8,
+    pub ratio: f64,
+}
+
+#[derive(Config, Debug, Default)]
+pub struct WorkerConfig {
+    pub name: String,
+    #[config(flatten)]
+    pub limits: LimitsConfig,
+}
+
+#[derive(Config, Debug, Default)]
+pub struct PoolConfig {
+    #[config(nest)]
+    pub worker: WorkerConfig,
+}
+
+#[test]
+fn test_flatten_coerces_strings() {
+    let layer = <WorkerConfig as Confgr>::Layer::from_str(
+        "name = \"worker\"\nlevel = \"7\"\nratio = \"0.5\"",
+        Format::Toml,
+    )
+    .unwrap();
+    let config = WorkerConfig::from(layer.merge(Default::default()));
+
+    assert_eq!(config.name, "worker");
+    assert_eq!(config.limits.level, 7);
+    assert_eq!(config.limits.ratio, 0.5);
+
+    let layer =
+        <PoolConfig as Confgr>::Layer::from_str("[worker]\nlevel = \"3\"", Format::Toml).unwrap();
+    let config = PoolConfig::from(layer.merge(Default::default()));
+
+    assert_eq!(config.worker.limits.level, 3);
+}
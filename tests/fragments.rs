@@ -0,0 +1,60 @@
+use confgr::prelude::*;
+
+use std::fs;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(
+    default_path = "tests/common/fragments.toml",
+    dir = "tests/common/conf.d"
+)]
+pub struct FragmentConfig {
+    pub name: String,
+    pub port: u32,
+    pub timeout: u64,
+    pub retries: u32,
+}
+
+impl Default for FragmentConfig {
+    fn default() -> Self {
+        Self {
+            name: "DefaultName".to_string(),
+            port: 3000,
+            timeout: 100,
+            retries: 3,
+        }
+    }
+}
+
+fn write_file(path: &str, contents: &str) {
+    let mut file = fs::File::create(path).unwrap();
+    writeln!(file, "{}", contents).unwrap();
+}
+
+#[test]
+fn test_fragments_merged_in_order() {
+    fs::create_dir_all("tests/common/conf.d").unwrap();
+    write_file(
+        "tests/common/fragments.toml",
+        "name = \"MainName\"\nport = 4000\ntimeout = 400",
+    );
+    write_file(
+        "tests/common/conf.d/10-base.toml",
+        "name = \"BaseName\"\nport = 5000",
+    );
+    write_file("tests/common/conf.d/20-local.yaml", "name: LocalName");
+    write_file("tests/common/conf.d/README.md", "not a fragment");
+
+    let paths = FragmentConfig::get_file_paths();
+    assert_eq!(paths.len(), 3);
+
+    let config = FragmentConfig::load_config();
+
+    assert_eq!(config.name, "LocalName");
+    assert_eq!(config.port, 5000);
+    assert_eq!(config.timeout, 400);
+    assert_eq!(config.retries, 3);
+
+    fs::remove_dir_all("tests/common/conf.d").unwrap();
+    fs::remove_file("tests/common/fragments.toml").unwrap();
+}
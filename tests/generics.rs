@@ -0,0 +1,53 @@
+use confgr::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+pub trait Backend {
+    const DRIVER: &'static str;
+}
+
+#[derive(Debug, Clone)]
+pub struct Postgres;
+
+impl Backend for Postgres {
+    const DRIVER: &'static str = "postgres";
+}
+
+#[derive(Config, Debug)]
+#[config(prefix = "POOL")]
+pub struct PoolConfig<'a, T: Backend, S>
+where
+    S: Clone + Debug,
+{
+    #[config(skip)]
+    name: Cow<'a, str>,
+    size: S,
+    backend: PhantomData<T>,
+}
+
+impl<T: Backend, S: Clone + Debug + From<u8>> Default for PoolConfig<'_, T, S> {
+    fn default() -> Self {
+        Self {
+            name: Cow::Borrowed(T::DRIVER),
+            size: S::from(4),
+            backend: PhantomData,
+        }
+    }
+}
+
+#[test]
+fn test_generic_config() {
+    let vars = HashMap::from([("POOL_SIZE".to_string(), "16".to_string())]);
+
+    let config = PoolConfig::<Postgres, u32>::load_config_with_env(|key| vars.get(key).cloned());
+    assert_eq!(config.name, "postgres");
+    assert_eq!(config.size, 16);
+
+    let config =
+        PoolConfig::<Postgres, u64>::load_config_from_str("name = 'primary'", Format::Toml)
+            .unwrap();
+    assert_eq!(config.name, "primary");
+    assert_eq!(config.size, 4);
+}
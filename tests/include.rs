@@ -0,0 +1,119 @@
+use confgr::core::ConfgrError;
+use confgr::prelude::*;
+
+use std::fs;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(default_path = "tests/common/include/main.toml", includes)]
+pub struct IncludeConfig {
+    pub name: String,
+    pub port: u32,
+    pub secret: String,
+}
+
+impl Default for IncludeConfig {
+    fn default() -> Self {
+        Self {
+            name: "DefaultName".to_string(),
+            port: 3000,
+            secret: "DefaultSecret".to_string(),
+        }
+    }
+}
+
+#[derive(Config, Default, Debug)]
+#[config(default_path = "tests/common/include_cycle/a.toml", includes)]
+pub struct IncludeCycleConfig {
+    pub name: String,
+}
+
+#[derive(Config, Default, Debug)]
+#[config(default_path = "tests/common/include_field.toml", strict)]
+pub struct IncludeFieldConfig {
+    #[config(skip)]
+    pub include: Vec<String>,
+}
+
+fn write_file(path: &str, contents: &str) {
+    fs::create_dir_all(std::path::Path::new(path).parent().unwrap()).unwrap();
+    let mut file = fs::File::create(path).unwrap();
+    writeln!(file, "{}", contents).unwrap();
+}
+
+#[test]
+fn test_includes_are_merged() {
+    write_file(
+        "tests/common/include/main.toml",
+        "include = [\"shared/common.toml\"]\nname = \"MainName\"",
+    );
+    write_file(
+        "tests/common/include/shared/common.toml",
+        "include = \"secrets.toml\"\nname = \"CommonName\"\nport = 4000",
+    );
+    write_file(
+        "tests/common/include/shared/secrets.toml",
+        "port = 5000\nsecret = \"IncludedSecret\"",
+    );
+
+    let config = IncludeConfig::load_config();
+
+    assert_eq!(config.name, "MainName");
+    assert_eq!(config.port, 4000);
+    assert_eq!(config.secret, "IncludedSecret");
+
+    fs::remove_file("tests/common/include/shared/secrets.toml").unwrap();
+
+    match IncludeConfig::deserialize_from_file() {
+        Err(ConfgrError::MissingInclude(path, chain)) => {
+            assert!(path.ends_with("secrets.toml"));
+            assert!(chain.ends_with("shared/common.toml"));
+        }
+        other => panic!("Expected a missing include error, got {:?}", other),
+    }
+
+    fs::remove_dir_all("tests/common/include").unwrap();
+}
+
+#[test]
+fn test_include_cycle_is_detected() {
+    write_file(
+        "tests/common/include_cycle/a.toml",
+        "include = [\"b.toml\"]\nname = \"A\"",
+    );
+    write_file(
+        "tests/common/include_cycle/b.toml",
+        "include = [\"a.toml\"]\nname = \"B\"",
+    );
+
+    match IncludeCycleConfig::deserialize_from_file() {
+        Err(ConfgrError::IncludeCycle(chain)) => assert_eq!(
+            chain,
+            "tests/common/include_cycle/a.toml -> tests/common/include_cycle/b.toml -> tests/common/include_cycle/a.toml"
+        ),
+        other => panic!("Expected an include cycle error, got {:?}", other),
+    }
+
+    assert!(matches!(
+        IncludeCycleConfig::try_load_config(),
+        Err(ConfgrError::IncludeCycle(_))
+    ));
+
+    let config = IncludeCycleConfig::load_config();
+    assert_eq!(config.name, "");
+
+    fs::remove_dir_all("tests/common/include_cycle").unwrap();
+}
+
+#[test]
+fn test_include_key_is_a_field_without_includes() {
+    write_file(
+        "tests/common/include_field.toml",
+        "include = [\"src/**/*.rs\"]",
+    );
+
+    let config = IncludeFieldConfig::try_load_config();
+    fs::remove_file("tests/common/include_field.toml").unwrap();
+
+    assert_eq!(config.unwrap().include, vec!["src/**/*.rs".to_string()]);
+}
@@ -0,0 +1,138 @@
+use confgr::core::{ConfgrError, Format};
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Default, Debug)]
+#[config(interpolate)]
+pub struct InterpolateConfig {
+    pub data_dir: String,
+    pub cache_dir: String,
+    pub port: u32,
+    pub literal: String,
+    #[config(nest)]
+    pub server: InterpolateServer,
+}
+
+#[derive(Config, Default, Debug)]
+pub struct InterpolateServer {
+    pub host: String,
+    pub url: String,
+}
+
+#[derive(Config, Default, Debug)]
+#[config(interpolate, path = "tests/common/interpolate_load.toml")]
+pub struct InterpolateFileConfig {
+    pub data_dir: String,
+    pub cache_dir: String,
+}
+
+#[derive(Config, Default, Debug)]
+#[config(
+    interpolate,
+    prefix = "INTERPOLATE_ENV",
+    env_json = "INTERPOLATE_ENV_JSON"
+)]
+pub struct InterpolateEnvConfig {
+    pub password: String,
+    pub token: String,
+}
+
+#[derive(Config, Default, Debug)]
+pub struct PlainConfig {
+    pub data_dir: String,
+}
+
+#[test]
+fn test_values_are_interpolated() {
+    let vars = HashMap::from([
+        ("INTERPOLATE_HOME".to_string(), "/home/confgr".to_string()),
+        ("INTERPOLATE_PORT".to_string(), "8080".to_string()),
+    ]);
+
+    let layer = <InterpolateConfig as Confgr>::Layer::from_str_with(
+        r#"
+        data_dir = "${INTERPOLATE_DATA:-/var/lib/app}"
+        cache_dir = "${INTERPOLATE_HOME}/.cache"
+        port = "${INTERPOLATE_PORT}"
+        literal = "$${NOT_INTERPOLATED}"
+
+        [server]
+        host = "localhost"
+        url = "http://${server.host}:${INTERPOLATE_PORT}"
+        "#,
+        Format::Toml,
+        |key| vars.get(key).cloned(),
+    )
+    .expect("Failed to interpolate layer");
+
+    assert_eq!(layer.data_dir.as_deref(), Some("/var/lib/app"));
+    assert_eq!(layer.cache_dir.as_deref(), Some("/home/confgr/.cache"));
+    assert_eq!(layer.port, Some(8080));
+    assert_eq!(layer.literal.as_deref(), Some("${NOT_INTERPOLATED}"));
+    assert_eq!(layer.server.url.as_deref(), Some("http://localhost:8080"));
+
+    let plain = <PlainConfig as Confgr>::Layer::from_str(
+        r#"data_dir = "${INTERPOLATE_HOME}""#,
+        Format::Toml,
+    )
+    .unwrap();
+    assert_eq!(plain.data_dir.as_deref(), Some("${INTERPOLATE_HOME}"));
+}
+
+#[test]
+fn test_load_config_interpolates_from_lookup() {
+    std::fs::write(
+        "tests/common/interpolate_load.toml",
+        "data_dir = \"${INTERPOLATE_ROOT}/data\"\ncache_dir = \"${data_dir}/cache\"\n",
+    )
+    .unwrap();
+
+    let vars = HashMap::from([("INTERPOLATE_ROOT".to_string(), "/srv".to_string())]);
+    let config = InterpolateFileConfig::try_load_config_with_env(|key| vars.get(key).cloned());
+    let undefined = InterpolateFileConfig::try_load_config_with_env_map(&HashMap::new());
+
+    std::fs::remove_file("tests/common/interpolate_load.toml").unwrap();
+
+    let config = config.unwrap();
+    assert_eq!(config.data_dir, "/srv/data");
+    assert_eq!(config.cache_dir, "/srv/data/cache");
+    assert!(
+        matches!(undefined, Err(ConfgrError::Interpolation(message)) if message.contains("INTERPOLATE_ROOT"))
+    );
+}
+
+#[test]
+fn test_interpolation_errors() {
+    let undefined = <InterpolateConfig as Confgr>::Layer::from_str(
+        r#"data_dir = "${INTERPOLATE_UNDEFINED}""#,
+        Format::Toml,
+    );
+    assert!(
+        matches!(undefined, Err(ConfgrError::Interpolation(message)) if message.contains("INTERPOLATE_UNDEFINED"))
+    );
+
+    let cycle = <InterpolateConfig as Confgr>::Layer::from_str(
+        r#"
+        [server]
+        host = "${server.url}"
+        url = "${server.host}"
+        "#,
+        Format::Toml,
+    );
+    assert!(matches!(cycle, Err(ConfgrError::Interpolation(message)) if message.contains("cycle")));
+}
+
+#[test]
+fn test_env_values_are_not_interpolated() {
+    let vars = HashMap::from([
+        ("INTERPOLATE_ENV__PASSWORD".to_string(), "p${x".to_string()),
+        (
+            "INTERPOLATE_ENV_JSON".to_string(),
+            r#"{"token": "$${literal}"}"#.to_string(),
+        ),
+    ]);
+    let config = InterpolateEnvConfig::try_load_config_with_env_map(&vars).unwrap();
+
+    assert_eq!(config.password, "p${x");
+    assert_eq!(config.token, "$${literal}");
+}
@@ -0,0 +1,44 @@
+use confgr::core::Confgr;
+use std::collections::HashMap;
+
+mod db {
+    #[derive(confgr::derive::Config, Debug)]
+    #[config(prefix = "MOD_DB")]
+    pub struct DbConfig {
+        pub url: String,
+        pub pool: u32,
+    }
+
+    impl Default for DbConfig {
+        fn default() -> Self {
+            Self {
+                url: "postgres://localhost".to_string(),
+                pool: 4,
+            }
+        }
+    }
+}
+
+mod app {
+    #[derive(confgr::derive::Config, Debug, Default)]
+    #[config(prefix = "MOD_APP")]
+    pub struct AppConfig {
+        pub name: String,
+        #[config(nest)]
+        pub db: crate::db::DbConfig,
+    }
+}
+
+#[test]
+fn test_nested_type_from_other_module() {
+    let vars = HashMap::from([
+        ("MOD_APP_NAME".to_string(), "modules".to_string()),
+        ("MOD_DB_POOL".to_string(), "16".to_string()),
+    ]);
+
+    let config = app::AppConfig::load_config_with_env(|key| vars.get(key).cloned());
+
+    assert_eq!(config.name, "modules");
+    assert_eq!(config.db.url, "postgres://localhost");
+    assert_eq!(config.db.pool, 16);
+}
@@ -0,0 +1,63 @@
+use confgr::prelude::*;
+
+#[derive(Config, Debug, Default)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub idle_timeout: u64,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(rename_all = "camelCase")]
+pub struct CacheConfig {
+    pub entry_limit: u32,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(rename_all = "kebab-case", strict)]
+pub struct RenamedConfig {
+    pub listen_addr: String,
+    #[config(name = "log_level")]
+    pub verbosity: String,
+    #[config(nest)]
+    pub connection_pool: PoolConfig,
+    #[config(nest)]
+    pub cache: CacheConfig,
+}
+
+#[test]
+fn test_rename_all() {
+    let layer = <RenamedConfig as Confgr>::Layer::from_str(
+        r#"
+        listen-addr: "0.0.0.0:80"
+        log_level: debug
+        connection-pool:
+          max-connections: 16
+          idle_timeout: 30
+        cache:
+          entryLimit: 64
+        "#,
+        Format::Yaml,
+    )
+    .unwrap();
+
+    assert_eq!(layer.listen_addr, Some("0.0.0.0:80".to_string()));
+    assert_eq!(layer.verbosity, Some("debug".to_string()));
+    assert_eq!(layer.connection_pool.max_connections, Some(16));
+    assert_eq!(layer.connection_pool.idle_timeout, Some(30));
+    assert_eq!(layer.cache.entry_limit, Some(64));
+}
+
+#[test]
+fn test_rename_all_unknown_keys() {
+    let result = <RenamedConfig as Confgr>::Layer::from_str(
+        r#"
+        listen_adr: "0.0.0.0:80"
+        "#,
+        Format::Yaml,
+    );
+
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("did you mean 'listen-addr'?"));
+}
@@ -0,0 +1,62 @@
+use confgr::prelude::*;
+use std::io::Cursor;
+
+#[derive(Config, Debug)]
+#[config(prefix = "SOURCE")]
+struct SourceConfig {
+    name: String,
+    port: u32,
+    #[config(nest)]
+    database: SourceDatabase,
+}
+
+impl Default for SourceConfig {
+    fn default() -> Self {
+        Self {
+            name: "DefaultName".to_string(),
+            port: 3000,
+            database: SourceDatabase::default(),
+        }
+    }
+}
+
+#[derive(Config, Default, Debug)]
+struct SourceDatabase {
+    #[config(skip)]
+    host: String,
+}
+
+#[test]
+fn test_layer_from_str() {
+    let layer = <SourceConfig as Confgr>::Layer::from_str(
+        r#"{ "name": "JsonName", "database": { "host": "db.local" } }"#,
+        Format::Json,
+    )
+    .expect("Failed to deserialize layer");
+
+    assert_eq!(layer.name, Some("JsonName".to_string()));
+    assert_eq!(layer.port, None);
+    assert_eq!(layer.database.host, Some("db.local".to_string()));
+}
+
+#[test]
+fn test_layer_from_reader() {
+    let reader = Cursor::new("name: YamlName\nport: 4000\n");
+    let layer = <SourceConfig as Confgr>::Layer::from_reader(reader, Format::Yaml)
+        .expect("Failed to deserialize layer");
+
+    assert_eq!(layer.name, Some("YamlName".to_string()));
+    assert_eq!(layer.port, Some(4000));
+}
+
+#[test]
+fn test_load_config_from_str() {
+    let config = SourceConfig::load_config_from_str("port = 5000", Format::Toml)
+        .expect("Failed to load configuration");
+
+    assert_eq!(config.name, "DefaultName");
+    assert_eq!(config.port, 5000);
+    assert_eq!(config.database.host, "");
+
+    assert!(SourceConfig::load_config_from_str("port = ", Format::Toml).is_err());
+}
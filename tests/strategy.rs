@@ -0,0 +1,151 @@
+use confgr::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use std::fs::File;
+use std::io::Write;
+
+#[derive(Config, Debug)]
+#[config(path = "tests/common/strategy.toml")]
+pub struct StrategyConfig {
+    #[config(skip, merge = "append")]
+    pub features: Vec<String>,
+    #[config(skip, merge = "union")]
+    pub parameters: HashMap<String, String>,
+    #[config(skip, merge = "replace")]
+    pub hosts: Vec<String>,
+    #[config(skip)]
+    pub ports: Vec<u32>,
+}
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            features: vec!["default".to_string()],
+            parameters: HashMap::from([
+                ("retries".to_string(), "3".to_string()),
+                ("timeout".to_string(), "100".to_string()),
+            ]),
+            hosts: vec!["localhost".to_string()],
+            ports: vec![80],
+        }
+    }
+}
+
+#[test]
+fn test_merge_strategies() {
+    let mut file = File::create("tests/common/strategy.toml").unwrap();
+    writeln!(
+        file,
+        r#"
+        features = ["file"]
+        hosts = ["example.com"]
+        ports = [8080]
+
+        [parameters]
+        timeout = "400"
+        mode = "fast"
+        "#
+    )
+    .unwrap();
+
+    let config = StrategyConfig::load_config();
+
+    assert_eq!(config.features, vec!["file"]);
+    assert!(!config.parameters.contains_key("retries"));
+    assert_eq!(config.parameters["timeout"], "400");
+    assert_eq!(config.parameters["mode"], "fast");
+    assert_eq!(config.hosts, vec!["example.com"]);
+    assert_eq!(config.ports, vec![8080]);
+
+    let layer = <StrategyConfig as Confgr>::Layer::from_str(
+        "features = [\"override\"]\n[parameters]\nmode = \"slow\"",
+        Format::Toml,
+    )
+    .unwrap();
+    let merged = layer.merge(StrategyConfig::deserialize_from_file().unwrap());
+    assert_eq!(merged.features.unwrap(), vec!["file", "override"]);
+    assert_eq!(merged.parameters.clone().unwrap()["timeout"], "400");
+    assert_eq!(merged.parameters.unwrap()["mode"], "slow");
+
+    let defaults = <StrategyConfig as Confgr>::Layer::default();
+    let filled = <StrategyConfig as Confgr>::Layer::empty().fill(defaults);
+    assert_eq!(filled.features.unwrap(), vec!["default"]);
+
+    std::fs::remove_file("tests/common/strategy.toml").unwrap();
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Upstream {
+    pub host: String,
+    pub port: u16,
+}
+
+fn dedupe_by_host(
+    higher: Option<Vec<Upstream>>,
+    lower: Option<Vec<Upstream>>,
+) -> Option<Vec<Upstream>> {
+    let mut upstreams = higher.unwrap_or_default();
+    for upstream in lower.into_iter().flatten() {
+        if !upstreams
+            .iter()
+            .any(|existing| existing.host == upstream.host)
+        {
+            upstreams.push(upstream);
+        }
+    }
+    Some(upstreams)
+}
+
+mod flags {
+    pub fn union(higher: Option<u32>, lower: Option<u32>) -> Option<u32> {
+        match (higher, lower) {
+            (Some(higher), Some(lower)) => Some(higher | lower),
+            (higher, lower) => higher.or(lower),
+        }
+    }
+}
+
+#[derive(Config, Debug, Default)]
+#[config(path = "tests/common/merge_with.toml", prefix = "MERGE_WITH")]
+pub struct MergeWithConfig {
+    #[config(skip, merge_with = "dedupe_by_host")]
+    pub upstreams: Vec<Upstream>,
+    #[config(merge_with = "flags::union")]
+    pub flags: u32,
+}
+
+#[test]
+fn test_merge_with_custom_functions() {
+    let mut file = File::create("tests/common/merge_with.toml").unwrap();
+    writeln!(
+        file,
+        r#"
+        flags = 1
+        upstreams = [
+            {{ host = "a.local", port = 80 }},
+            {{ host = "b.local", port = 80 }},
+        ]
+        "#
+    )
+    .unwrap();
+    std::env::set_var("MERGE_WITH_FLAGS", "4");
+
+    let layer = <MergeWithConfig as Confgr>::Layer::from_str(
+        r#"upstreams = [{ host = "a.local", port = 8080 }]"#,
+        Format::Toml,
+    )
+    .unwrap();
+    let merged = layer.merge(MergeWithConfig::deserialize_from_file().unwrap());
+    let upstreams = merged.upstreams.unwrap();
+
+    assert_eq!(upstreams.len(), 2);
+    assert_eq!(upstreams[0].port, 8080);
+    assert_eq!(upstreams[1].host, "b.local");
+
+    let config = MergeWithConfig::load_config();
+    assert_eq!(config.flags, 5);
+
+    std::env::remove_var("MERGE_WITH_FLAGS");
+    std::fs::remove_file("tests/common/merge_with.toml").unwrap();
+}
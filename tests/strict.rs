@@ -0,0 +1,95 @@
+use confgr::core::{collect_warnings, set_unknown_keys, ConfgrError, UnknownKeys};
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, Default)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub pool: u32,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(strict, includes)]
+pub struct StrictConfig {
+    pub port: u16,
+    #[config(skip)]
+    pub labels: HashMap<String, String>,
+    #[config(nest)]
+    pub database: DatabaseConfig,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(strict, path = "tests/common/strict_load.toml")]
+pub struct StrictFileConfig {
+    pub port: u16,
+}
+
+#[derive(Config, Debug, Default)]
+pub struct LenientConfig {
+    pub port: u16,
+}
+
+#[test]
+fn test_strict_rejects_unknown_keys() {
+    let result = <StrictConfig as Confgr>::Layer::from_str(
+        r#"
+        include = []
+        prot = 8080
+
+        [labels]
+        anything = "goes"
+
+        [database]
+        url = "postgres://localhost"
+        pol = 4
+        "#,
+        Format::Toml,
+    );
+
+    match result {
+        Err(ConfgrError::UnknownKeys(keys)) => assert_eq!(
+            keys,
+            "unknown key 'database.pol', did you mean 'database.pool'?; \
+             unknown key 'prot', did you mean 'port'?"
+        ),
+        other => panic!("Expected unknown keys, got {:?}", other),
+    }
+
+    let layer = <StrictConfig as Confgr>::Layer::from_str("port = 8080", Format::Toml).unwrap();
+    assert_eq!(layer.port, Some(8080));
+}
+
+#[test]
+fn test_unknown_keys_runtime_policy() {
+    set_unknown_keys(UnknownKeys::Warn);
+    let (layer, warnings) = collect_warnings(|| {
+        <LenientConfig as Confgr>::Layer::from_str("prot = 8080", Format::Toml)
+    });
+    set_unknown_keys(UnknownKeys::Ignore);
+
+    assert!(layer.is_ok());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].field, "prot");
+}
+
+#[test]
+fn test_strict_load_config_surfaces_unknown_keys() {
+    std::fs::write(
+        "tests/common/strict_load.toml",
+        "port = 8080\nprot = 9090\n",
+    )
+    .unwrap();
+
+    let result = StrictFileConfig::try_load_config();
+    let load = std::panic::catch_unwind(StrictFileConfig::load_config);
+
+    std::fs::remove_file("tests/common/strict_load.toml").unwrap();
+
+    match result {
+        Err(ConfgrError::UnknownKeys(keys)) => {
+            assert_eq!(keys, "unknown key 'prot', did you mean 'port'?")
+        }
+        other => panic!("Expected unknown keys, got {:?}", other),
+    }
+    assert!(load.is_err());
+}
@@ -0,0 +1,48 @@
+use confgr::prelude::*;
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "DB")]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub pool: u32,
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "UNKNOWN")]
+pub struct UnknownEnvConfig {
+    pub port: u16,
+    #[config(env_alias = "UNKNOWN_HOSTNAME")]
+    pub host: String,
+    #[config(nest)]
+    pub database: DatabaseConfig,
+}
+
+#[test]
+fn test_find_unknown_env_vars() {
+    let unknown = <UnknownEnvConfig as Confgr>::Layer::find_unknown_env_vars_in([
+        "UNKNOWN_PROTT",
+        "UNKNOWN_PORT",
+        "UNKNOWN_HOSTNAME",
+        "DB_POL",
+        "DB_URL",
+        "PATH",
+    ]);
+
+    assert_eq!(unknown.len(), 2);
+    assert_eq!(unknown[0].key, "DB_POL");
+    assert_eq!(unknown[0].suggestion.as_deref(), Some("DB_POOL"));
+    assert_eq!(
+        unknown[1].to_string(),
+        "unknown key 'UNKNOWN_PROTT', did you mean 'UNKNOWN_PORT'?"
+    );
+}
+
+#[test]
+fn test_find_unknown_env_vars_in_process_env() {
+    std::env::set_var("UNKNOWN_DATABSE", "postgres://localhost");
+
+    let unknown = UnknownEnvConfig::find_unknown_env_vars();
+    assert!(unknown.iter().any(|key| key.key == "UNKNOWN_DATABSE"));
+
+    std::env::remove_var("UNKNOWN_DATABSE");
+}
@@ -0,0 +1,90 @@
+use confgr::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Config, Debug, PartialEq)]
+#[config(prefix = "APP_STORAGE")]
+pub enum Storage {
+    S3 {
+        bucket: String,
+        region: String,
+    },
+    Local {
+        path: String,
+    },
+    #[config(name = "mem")]
+    Memory,
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::Local {
+            path: "/var/lib/app".to_string(),
+        }
+    }
+}
+
+#[derive(Config, Debug, PartialEq)]
+#[config(prefix = "APP_PORT")]
+pub struct Port(u16);
+
+impl Default for Port {
+    fn default() -> Self {
+        Self(8080)
+    }
+}
+
+#[derive(Config, Debug, Default)]
+#[config(prefix = "APP")]
+pub struct App {
+    #[config(nest)]
+    storage: Storage,
+    #[config(nest)]
+    port: Port,
+}
+
+#[test]
+fn test_enum_variant_from_file_and_env() {
+    let config = App::load_config_from_str(
+        r#"
+        port = 9000
+
+        [storage]
+        kind = "s3"
+        bucket = "assets"
+        "#,
+        Format::Toml,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.storage,
+        Storage::S3 {
+            bucket: "assets".to_string(),
+            region: String::new(),
+        }
+    );
+    assert_eq!(config.port, Port(9000));
+
+    let vars = HashMap::from([
+        ("APP_STORAGE_KIND".to_string(), "LOCAL".to_string()),
+        ("APP_PORT".to_string(), "3000".to_string()),
+    ]);
+    let config = App::load_config_with_env(|key| vars.get(key).cloned());
+
+    assert_eq!(
+        config.storage,
+        Storage::Local {
+            path: "/var/lib/app".to_string(),
+        }
+    );
+    assert_eq!(config.port, Port(3000));
+}
+
+#[test]
+fn test_enum_unit_variant_and_unknown_tag() {
+    let config = App::load_config_from_str(r#"storage = "mem""#, Format::Toml).unwrap();
+    assert_eq!(config.storage, Storage::Memory);
+
+    let result = App::load_config_from_str(r#"storage = "disk""#, Format::Toml);
+    assert!(result.is_err());
+}